@@ -12,6 +12,9 @@ fn skiplist_get_benchmark(c: &mut Criterion) {
     }
 
     c.bench_function("skiplist get 7777", |b| b.iter(|| sk.get(black_box(7777))));
+    c.bench_function("skiplist get_unchecked 7777", |b| {
+        b.iter(|| unsafe { sk.get_unchecked(black_box(7777)) })
+    });
 }
 
 criterion_group!(benches, skiplist_get_benchmark);
@@ -0,0 +1,400 @@
+//! A skiplist whose nodes live in a generational arena and are linked by
+//! indices, there is no unsafe code in it.
+//!
+//! It's slower than [`SkipList`](../skiplist/struct.SkipList.html) because every
+//! link is checked against the arena, but a stale link panics instead of
+//! reading freed memory, which also makes it an oracle for testing the pointer
+//! based skiplist.
+//!
+//! It's a separate type rather than a backend of `SkipList`: the containers
+//! built on `SkipList` reach into its nodes and pointers directly, so swapping
+//! the links under them is out of scope here. Only the indexed operations are
+//! provided.
+#![forbid(unsafe_code)]
+
+use std::iter::FusedIterator;
+
+use crate::level_generator::LevelGenerator;
+
+/// Index of a node in the arena, with the generation of the slot when the
+/// node was put in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Link {
+    index: u32,
+    generation: u32,
+}
+
+struct ArenaNode<V> {
+    value: Option<V>,
+    links: Vec<Option<Link>>,
+    links_len: Vec<usize>,
+}
+
+impl<V> ArenaNode<V> {
+    fn new(value: Option<V>, levels: usize) -> Self {
+        ArenaNode {
+            value,
+            links: vec![None; levels],
+            links_len: vec![0; levels],
+        }
+    }
+}
+
+struct Slot<V> {
+    generation: u32,
+    node: Option<ArenaNode<V>>,
+}
+
+struct Arena<V> {
+    slots: Vec<Slot<V>>,
+    free: Vec<u32>,
+}
+
+impl<V> Arena<V> {
+    fn new() -> Self {
+        Arena {
+            slots: vec![],
+            free: vec![],
+        }
+    }
+
+    fn alloc(&mut self, node: ArenaNode<V>) -> Link {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.node = Some(node);
+                Link {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                assert!(self.slots.len() < u32::MAX as usize, "arena is full");
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                Link {
+                    index: self.slots.len() as u32 - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    fn free(&mut self, link: Link) -> ArenaNode<V> {
+        let slot = &mut self.slots[link.index as usize];
+        assert_eq!(slot.generation, link.generation, "link to a freed node");
+        let node = slot.node.take().expect("link to a freed node");
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(link.index);
+        node
+    }
+
+    fn get(&self, link: Link) -> &ArenaNode<V> {
+        let slot = &self.slots[link.index as usize];
+        assert_eq!(slot.generation, link.generation, "link to a freed node");
+        slot.node.as_ref().expect("link to a freed node")
+    }
+
+    fn get_mut(&mut self, link: Link) -> &mut ArenaNode<V> {
+        let slot = &mut self.slots[link.index as usize];
+        assert_eq!(slot.generation, link.generation, "link to a freed node");
+        slot.node.as_mut().expect("link to a freed node")
+    }
+}
+
+/// A skiplist linked by arena indices, it has the same index semantics as
+/// `SkipList`.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::arena_skiplist::ArenaSkipList;
+///
+/// let mut sk = ArenaSkipList::new();
+/// sk.push_back(1);
+/// sk.push_front(0);
+/// sk.insert(2, 2);
+/// assert_eq!(sk.get(1), Some(&1));
+/// assert_eq!(sk.remove(0), 0);
+/// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 2]);
+/// ```
+pub struct ArenaSkipList<V> {
+    arena: Arena<V>,
+    head: Link,
+    length: usize,
+    level_generator: LevelGenerator,
+}
+
+impl<V> ArenaSkipList<V> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::new())
+    }
+
+    pub fn with_level_generator(lg: LevelGenerator) -> Self {
+        let mut arena = Arena::new();
+        let head = arena.alloc(ArenaNode::new(None, 1));
+        ArenaSkipList {
+            arena,
+            head,
+            length: 0,
+            level_generator: lg,
+        }
+    }
+
+    /// Returns length of the skiplist
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Insert value at the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
+    pub fn insert(&mut self, index: usize, value: V) {
+        if index > self.length {
+            panic!("Index out of bounds.");
+        }
+
+        let height = self.level_generator.choose() + 1;
+        while self.arena.get(self.head).links.len() < height {
+            let head = self.arena.get_mut(self.head);
+            head.links.push(None);
+            head.links_len.push(0);
+        }
+
+        let actual_index = index + 1;
+        let (prevs, poses) = self._prevs_of(actual_index);
+        let mut node = ArenaNode::new(Some(value), height);
+        for level in 0..height {
+            let prev = self.arena.get(prevs[level]);
+            node.links[level] = prev.links[level];
+            if prev.links[level].is_some() {
+                node.links_len[level] = poses[level] + prev.links_len[level] + 1 - actual_index;
+            }
+        }
+
+        let link = self.arena.alloc(node);
+        for (level, &prev_link) in prevs.iter().enumerate() {
+            let prev = self.arena.get_mut(prev_link);
+            if level < height {
+                prev.links[level] = Some(link);
+                prev.links_len[level] = actual_index - poses[level];
+            } else if prev.links[level].is_some() {
+                prev.links_len[level] += 1;
+            }
+        }
+        self.length += 1;
+    }
+
+    /// Remove value at the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    pub fn remove(&mut self, index: usize) -> V {
+        if index >= self.length {
+            panic!("Index out of bounds.");
+        }
+
+        let (prevs, _) = self._prevs_of(index + 1);
+        let link = self.arena.get(prevs[0]).links[0].expect("node at the index must exist");
+        let node = self.arena.free(link);
+        for (level, &prev_link) in prevs.iter().enumerate() {
+            let prev = self.arena.get_mut(prev_link);
+            if prev.links[level] == Some(link) {
+                prev.links[level] = node.links[level];
+                prev.links_len[level] = if node.links[level].is_some() {
+                    prev.links_len[level] + node.links_len[level] - 1
+                } else {
+                    0
+                };
+            } else if prev.links[level].is_some() {
+                prev.links_len[level] -= 1;
+            }
+        }
+        self.length -= 1;
+        node.value.expect("there must be value in a normal node")
+    }
+
+    /// Push value at the front
+    pub fn push_front(&mut self, value: V) {
+        self.insert(0, value);
+    }
+
+    /// Push value at the back
+    pub fn push_back(&mut self, value: V) {
+        self.insert(self.length, value);
+    }
+
+    /// Pop the first value
+    pub fn pop_front(&mut self) -> Option<V> {
+        if self.length == 0 {
+            return None;
+        }
+        Some(self.remove(0))
+    }
+
+    /// Pop the last value
+    pub fn pop_back(&mut self) -> Option<V> {
+        if self.length == 0 {
+            return None;
+        }
+        Some(self.remove(self.length - 1))
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        if index >= self.length {
+            return None;
+        }
+        let (prevs, _) = self._prevs_of(index + 1);
+        let link = self.arena.get(prevs[0]).links[0]?;
+        self.arena.get(link).value.as_ref()
+    }
+
+    /// Returns an iterator of the skiplist
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            arena: &self.arena,
+            next: self.arena.get(self.head).links[0],
+        }
+    }
+
+    // Returns the last node before the index (counting head as 0) in every
+    // level, and their indexes
+    fn _prevs_of(&self, actual_index: usize) -> (Vec<Link>, Vec<usize>) {
+        let levels = self.arena.get(self.head).links.len();
+        let mut prevs = vec![self.head; levels];
+        let mut poses = vec![0; levels];
+        let mut cur = self.head;
+        let mut cur_pos = 0;
+        for level in (0..levels).rev() {
+            loop {
+                let node = self.arena.get(cur);
+                match node.links[level] {
+                    Some(next) if cur_pos + node.links_len[level] < actual_index => {
+                        cur_pos += node.links_len[level];
+                        cur = next;
+                    }
+                    _ => break,
+                }
+            }
+            prevs[level] = cur;
+            poses[level] = cur_pos;
+        }
+        (prevs, poses)
+    }
+}
+
+impl<V: std::fmt::Debug> std::fmt::Debug for ArenaSkipList<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, V> {
+    arena: &'a Arena<V>,
+    next: Option<Link>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.arena.get(self.next?);
+        self.next = node.links[0];
+        node.value.as_ref()
+    }
+}
+
+impl<'a, V> FusedIterator for Iter<'a, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::skiplist::SkipList;
+
+    #[test]
+    fn same_as_skiplist() {
+        let mut arena_sk = ArenaSkipList::new();
+        let mut sk = SkipList::new();
+        for i in 0..3000usize {
+            let len = sk.len();
+            if i % 4 == 3 && len > 0 {
+                let index = (i * 7919) % len;
+                assert_eq!(arena_sk.remove(index), sk.remove(index));
+            } else {
+                let index = (i * 104_729) % (len + 1);
+                arena_sk.insert(index, i);
+                sk.insert(index, i);
+            }
+        }
+        assert_eq!(arena_sk.len(), sk.len());
+        assert!(arena_sk.iter().eq(sk.iter()));
+        for i in 0..sk.len() {
+            assert_eq!(arena_sk.get(i), sk.get(i));
+        }
+        assert_eq!(arena_sk.pop_back(), sk.pop_back());
+        assert_eq!(arena_sk.pop_front(), sk.pop_front());
+    }
+
+    #[test]
+    fn empty_list() {
+        let mut sk: ArenaSkipList<i32> = ArenaSkipList::new();
+        assert_eq!(sk.len(), 0);
+        assert_eq!(sk.get(0), None);
+        assert_eq!(sk.pop_front(), None);
+        assert_eq!(sk.pop_back(), None);
+        assert_eq!(sk.iter().next(), None);
+
+        sk.push_back(1);
+        assert_eq!(sk.pop_front(), Some(1));
+        assert_eq!(sk.get(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn insert_out_of_bounds() {
+        let mut sk = ArenaSkipList::new();
+        sk.push_back(0);
+        sk.insert(2, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds.")]
+    fn remove_out_of_bounds() {
+        let mut sk = ArenaSkipList::new();
+        sk.push_back(0);
+        sk.remove(1);
+    }
+
+    #[test]
+    fn slots_are_reused() {
+        let mut sk = ArenaSkipList::new();
+        for round in 0..3 {
+            for i in 0..100 {
+                sk.push_back(i);
+            }
+            assert_eq!(sk.arena.slots.len(), 101);
+            let values: Vec<_> = std::iter::from_fn(|| sk.pop_front()).collect();
+            assert_eq!(values, (0..100).collect::<Vec<_>>(), "round {}", round);
+        }
+        assert_eq!(sk.arena.free.len(), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "link to a freed node")]
+    fn stale_link_panics() {
+        let mut arena = Arena::new();
+        let link = arena.alloc(ArenaNode::new(Some(0), 1));
+        arena.free(link);
+        // the slot is reused by a new node of a newer generation
+        let new_link = arena.alloc(ArenaNode::new(Some(1), 1));
+        assert_eq!(new_link.index, link.index);
+        assert_eq!(arena.get(new_link).value, Some(1));
+        arena.get(link);
+    }
+}
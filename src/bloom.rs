@@ -0,0 +1,99 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A counting bloom filter, values can be removed from it.
+///
+/// A counter that reaches the maximum is never decreased, so the filter never
+/// reports a value that is in it as absent.
+pub(crate) struct BloomFilter<T: ?Sized> {
+    counters: Vec<u8>,
+    hashes: u64,
+    // hash function of the element type, it's captured where `T: Hash` is known
+    hash: fn(&T) -> u64,
+}
+
+pub(crate) fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T: ?Sized> BloomFilter<T> {
+    /// Create a filter for about `expected_items` elements whose false positive
+    /// rate is about `false_positive_rate`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not in `(0, 1)`
+    pub(crate) fn new(expected_items: usize, false_positive_rate: f64) -> Self
+    where
+        T: Hash,
+    {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "false positive rate should be in (0, 1)"
+        );
+        let ln2 = std::f64::consts::LN_2;
+        let items = expected_items.max(1) as f64;
+        let counters = (-items * false_positive_rate.ln() / (ln2 * ln2)).ceil().max(8.0);
+        let hashes = (counters / items * ln2).round().max(1.0);
+        BloomFilter {
+            counters: vec![0; counters as usize],
+            hashes: hashes as u64,
+            hash: hash_of::<T>,
+        }
+    }
+
+    /// Returns hash of a value of the element type
+    pub(crate) fn hash(&self, value: &T) -> u64 {
+        (self.hash)(value)
+    }
+
+    pub(crate) fn insert_hash(&mut self, hash: u64) {
+        for i in self._indexes(hash) {
+            self.counters[i] = self.counters[i].saturating_add(1);
+        }
+    }
+
+    pub(crate) fn remove_hash(&mut self, hash: u64) {
+        for i in self._indexes(hash) {
+            if self.counters[i] != u8::MAX {
+                self.counters[i] -= 1;
+            }
+        }
+    }
+
+    /// Returns false if a value of the hash is definitely not in the filter
+    pub(crate) fn may_contain_hash(&self, hash: u64) -> bool {
+        self._indexes(hash).all(|i| self.counters[i] > 0)
+    }
+
+    // double hashing, the i-th index is `h1 + i * h2`
+    fn _indexes(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let len = self.counters.len() as u64;
+        let h1 = hash & 0xffff_ffff;
+        let h2 = (hash >> 32) | 1;
+        (0..self.hashes).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let mut filter = BloomFilter::<u32>::new(100, 0.01);
+        for i in 0..100 {
+            filter.insert_hash(filter.hash(&i));
+        }
+        assert!((0..100).all(|i| filter.may_contain_hash(hash_of(&i))));
+
+        for i in 0..50 {
+            filter.remove_hash(filter.hash(&i));
+        }
+        assert!((50..100).all(|i| filter.may_contain_hash(hash_of(&i))));
+        let false_positives = (1000..2000).filter(|i| filter.may_contain_hash(hash_of(i))).count();
+        assert!(false_positives < 100);
+    }
+}
@@ -0,0 +1,494 @@
+use std::borrow::Borrow;
+
+use crate::ordered_skiplist::OrderedSkipList;
+use crate::skiplist::{Iter, SkipList};
+
+/// Which value to evict when a bounded container exceeds its maximum length
+pub enum EvictPolicy<V> {
+    /// Evict the first value
+    Front,
+    /// Evict the last value
+    Back,
+    /// Evict the value at the index returned by the function, the function is
+    /// called with all values including the newly inserted one
+    ///
+    /// # Panics
+    ///
+    /// The insertion panics if the returned index is out of bounds, the newly
+    /// inserted value is taken back out first so the maximum length still holds.
+    Custom(fn(&SkipList<V>) -> usize),
+}
+
+impl<V> Clone for EvictPolicy<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for EvictPolicy<V> {}
+
+impl<V> EvictPolicy<V> {
+    /// Evict a value from `sk`, which has just got a value inserted at the
+    /// index `inserted`
+    fn evict(&self, sk: &mut SkipList<V>, inserted: usize) -> V {
+        let index = match self {
+            EvictPolicy::Front => 0,
+            EvictPolicy::Back => sk.len() - 1,
+            EvictPolicy::Custom(choose) => choose(sk),
+        };
+        if index >= sk.len() {
+            let len = sk.len();
+            sk.remove(inserted);
+            panic!(
+                "index returned by the custom evict policy is {} but the length is {}",
+                index, len
+            );
+        }
+        sk.remove(index)
+    }
+}
+
+/// A skiplist that holds at most `max_len` values, a value is evicted by the
+/// policy when an insertion exceeds it.
+pub struct BoundedSkipList<V> {
+    sk: SkipList<V>,
+    max_len: usize,
+    policy: EvictPolicy<V>,
+}
+
+impl<V> BoundedSkipList<V> {
+    /// Create a bounded skiplist
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::bounded::{BoundedSkipList, EvictPolicy};
+    ///
+    /// // keep the latest 3 values
+    /// let mut sk = BoundedSkipList::with_max_len(3, EvictPolicy::Front);
+    /// for i in 0..3 {
+    ///     assert_eq!(sk.push_back(i), None);
+    /// }
+    /// assert_eq!(sk.push_back(3), Some(0));
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn with_max_len(max_len: usize, policy: EvictPolicy<V>) -> Self {
+        assert!(max_len > 0, "max_len should be greater than 0");
+        BoundedSkipList {
+            sk: SkipList::new(),
+            max_len,
+            policy,
+        }
+    }
+
+    /// Returns the maximum length
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns length of the skiplist
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Insert value at the index, returns the evicted value if the length
+    /// exceeds the maximum
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist, or if a custom evict
+    /// policy returns an out of bounds index, the value isn't inserted then
+    pub fn insert(&mut self, index: usize, value: V) -> Option<V> {
+        self.sk.insert(index, value);
+        self._evict(index)
+    }
+
+    /// Push value at the front, returns the evicted value if the length exceeds
+    /// the maximum
+    ///
+    /// # Panics
+    ///
+    /// Panics if a custom evict policy returns an out of bounds index
+    pub fn push_front(&mut self, value: V) -> Option<V> {
+        self.insert(0, value)
+    }
+
+    /// Push value at the back, returns the evicted value if the length exceeds
+    /// the maximum
+    ///
+    /// # Panics
+    ///
+    /// Panics if a custom evict policy returns an out of bounds index
+    pub fn push_back(&mut self, value: V) -> Option<V> {
+        self.insert(self.sk.len(), value)
+    }
+
+    /// Remove value at the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    pub fn remove(&mut self, index: usize) -> V {
+        self.sk.remove(index)
+    }
+
+    /// Pop the first value
+    pub fn pop_front(&mut self) -> Option<V> {
+        self.sk.pop_front()
+    }
+
+    /// Pop the last value
+    pub fn pop_back(&mut self) -> Option<V> {
+        self.sk.pop_back()
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Returns an iterator of the skiplist
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.sk.iter()
+    }
+
+    /// Returns the inner skiplist for reading
+    pub fn as_skiplist(&self) -> &SkipList<V> {
+        &self.sk
+    }
+
+    /// Returns the inner skiplist
+    pub fn into_inner(self) -> SkipList<V> {
+        self.sk
+    }
+
+    fn _evict(&mut self, inserted: usize) -> Option<V> {
+        if self.sk.len() <= self.max_len {
+            return None;
+        }
+        Some(self.policy.evict(&mut self.sk, inserted))
+    }
+}
+
+/// An ordered skiplist that holds at most `max_len` values, a value is evicted
+/// by the policy when an insertion exceeds it.
+pub struct BoundedOrderedSkipList<V: Ord> {
+    sk: OrderedSkipList<V>,
+    max_len: usize,
+    policy: EvictPolicy<V>,
+}
+
+impl<V: Ord> BoundedOrderedSkipList<V> {
+    /// Create a bounded ordered skiplist that doesn't allow duplicated values
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::bounded::{BoundedOrderedSkipList, EvictPolicy};
+    ///
+    /// // keep the greatest 3 values
+    /// let mut top = BoundedOrderedSkipList::with_max_len(3, EvictPolicy::Front);
+    /// for i in [5, 1, 8, 3, 9].iter() {
+    ///     top.insert(*i);
+    /// }
+    /// assert_eq!(top.iter().cloned().collect::<Vec<_>>(), vec![5, 8, 9]);
+    /// ```
+    pub fn with_max_len(max_len: usize, policy: EvictPolicy<V>) -> Self {
+        Self::with_config(false, max_len, policy)
+    }
+
+    /// Create a bounded ordered skiplist that allows duplicated values if `dup`
+    /// is true
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is 0
+    pub fn with_config(dup: bool, max_len: usize, policy: EvictPolicy<V>) -> Self {
+        assert!(max_len > 0, "max_len should be greater than 0");
+        let sk = if dup {
+            OrderedSkipList::new_duplicatable()
+        } else {
+            OrderedSkipList::new()
+        };
+        BoundedOrderedSkipList {
+            sk,
+            max_len,
+            policy,
+        }
+    }
+
+    /// Returns the maximum length
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Returns length of the ordered skiplist
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Insert value, returns the value that leaves the ordered skiplist: the old
+    /// value if it's not duplicatable and the value is duplicated, or the evicted
+    /// value if the length exceeds the maximum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a custom evict policy returns an out of bounds index, the value
+    /// isn't inserted then
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::bounded::{BoundedOrderedSkipList, EvictPolicy};
+    ///
+    /// let mut sk = BoundedOrderedSkipList::with_max_len(2, EvictPolicy::Back);
+    /// assert_eq!(sk.insert(1), None);
+    /// assert_eq!(sk.insert(3), None);
+    /// assert_eq!(sk.insert(2), Some(3));
+    /// ```
+    pub fn insert(&mut self, value: V) -> Option<V> {
+        // Where the value goes is only needed to take it back out when a custom
+        // policy returns a bad index.
+        let inserted = match self.policy {
+            EvictPolicy::Custom(_) if self.sk.len() >= self.max_len => {
+                self.sk.sk._seek_by(|v| v.cmp(&value), false).1
+            }
+            _ => 0,
+        };
+        if let Some(old) = self.sk.insert(value) {
+            return Some(old);
+        }
+        if self.sk.len() <= self.max_len {
+            return None;
+        }
+        Some(self.policy.evict(&mut self.sk.sk, inserted))
+    }
+
+    /// Remove the first value equal to q, returns it if it exists
+    pub fn remove_first<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.remove_first(q)
+    }
+
+    /// Pop the first value
+    pub fn pop_front(&mut self) -> Option<V> {
+        self.sk.pop_front()
+    }
+
+    /// Pop the last value
+    pub fn pop_back(&mut self) -> Option<V> {
+        self.sk.pop_back()
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Returns an iterator of the ordered skiplist
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.sk.iter()
+    }
+
+    /// Returns the inner ordered skiplist for reading
+    pub fn as_ordered_skiplist(&self) -> &OrderedSkipList<V> {
+        &self.sk
+    }
+
+    /// Returns the inner ordered skiplist
+    pub fn into_inner(self) -> OrderedSkipList<V> {
+        self.sk
+    }
+}
+
+/// Keeps the k largest or the k smallest values pushed to it, for streaming
+/// top-N computations. A value that can't get in is rejected without being
+/// inserted.
+pub struct TopK<V: Ord> {
+    sk: OrderedSkipList<V>,
+    k: usize,
+    largest: bool,
+}
+
+impl<V: Ord> TopK<V> {
+    /// Create a top-k list that keeps the k largest values
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::bounded::TopK;
+    ///
+    /// let mut top = TopK::largest(3);
+    /// for i in [5, 1, 8, 3, 9].iter() {
+    ///     top.push(*i);
+    /// }
+    /// assert_eq!(top.iter().cloned().collect::<Vec<_>>(), vec![5, 8, 9]);
+    /// assert_eq!(top.push(4), Some(4));
+    /// assert_eq!(top.push(6), Some(5));
+    /// ```
+    pub fn largest(k: usize) -> Self {
+        Self::with_order(k, true)
+    }
+
+    /// Create a top-k list that keeps the k smallest values
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::bounded::TopK;
+    ///
+    /// let mut top = TopK::smallest(2);
+    /// for i in [5, 1, 8, 3, 9].iter() {
+    ///     top.push(*i);
+    /// }
+    /// assert_eq!(top.iter().cloned().collect::<Vec<_>>(), vec![1, 3]);
+    /// assert_eq!(top.worst(), Some(&3));
+    /// ```
+    pub fn smallest(k: usize) -> Self {
+        Self::with_order(k, false)
+    }
+
+    fn with_order(k: usize, largest: bool) -> Self {
+        assert!(k > 0, "k should be greater than 0");
+        TopK {
+            sk: OrderedSkipList::new_duplicatable(),
+            k,
+            largest,
+        }
+    }
+
+    /// Returns k
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Returns number of values kept
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Push a value, returns the value that doesn't make it to the top k: the
+    /// pushed value if it's rejected, or the evicted value. A value equal to
+    /// the worst kept one is rejected when the list is full.
+    pub fn push(&mut self, value: V) -> Option<V> {
+        if self.sk.len() == self.k {
+            let worst = self.worst().expect("k is greater than 0");
+            let rejected = if self.largest {
+                value <= *worst
+            } else {
+                value >= *worst
+            };
+            if rejected {
+                return Some(value);
+            }
+        }
+
+        self.sk.insert(value);
+        if self.sk.len() <= self.k {
+            return None;
+        }
+        if self.largest {
+            self.sk.pop_front()
+        } else {
+            self.sk.pop_back()
+        }
+    }
+
+    /// Returns the worst kept value, which a pushed value has to beat once the
+    /// list is full
+    pub fn worst(&self) -> Option<&V> {
+        if self.largest {
+            self.sk.front()
+        } else {
+            self.sk.back()
+        }
+    }
+
+    /// Returns an iterator of kept values in ascending order
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.sk.iter()
+    }
+
+    /// Returns the kept values as an ordered skiplist
+    pub fn into_inner(self) -> OrderedSkipList<V> {
+        self.sk
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn custom_policy() {
+        // evict the value in the middle
+        let mut sk = BoundedSkipList::with_max_len(3, EvictPolicy::Custom(|sk| sk.len() / 2));
+        let evicted: Vec<_> = (0..6).filter_map(|i| sk.push_back(i)).collect();
+        assert_eq!(evicted, vec![2, 3, 4]);
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 5]);
+
+        let mut sk = BoundedOrderedSkipList::with_config(true, 2, EvictPolicy::Back);
+        assert_eq!(sk.insert(1), None);
+        assert_eq!(sk.insert(1), None);
+        assert_eq!(sk.insert(0), Some(1));
+        assert_eq!(sk.len(), 2);
+    }
+
+    #[test]
+    fn custom_policy_out_of_bounds() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut sk = BoundedSkipList::with_max_len(2, EvictPolicy::Custom(|sk| sk.len()));
+        sk.push_back(0);
+        sk.push_back(2);
+        let res = catch_unwind(AssertUnwindSafe(|| sk.insert(1, 1)));
+        assert!(res.is_err());
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 2]);
+
+        let mut sk = BoundedOrderedSkipList::with_config(true, 2, EvictPolicy::Custom(|_| 5));
+        sk.insert(0);
+        sk.insert(2);
+        let res = catch_unwind(AssertUnwindSafe(|| sk.insert(1)));
+        assert!(res.is_err());
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    fn top_k() {
+        let values: Vec<i32> = (0..200).map(|i| (i * 37) % 101).collect();
+        let mut largest = TopK::largest(10);
+        let mut smallest = TopK::smallest(10);
+        let mut left = 0;
+        for v in values.iter() {
+            left += largest.push(*v).is_some() as usize;
+            smallest.push(*v);
+        }
+        assert_eq!(left, values.len() - 10);
+
+        let mut sorted = values.clone();
+        sorted.sort();
+        let kept: Vec<_> = largest.iter().cloned().collect();
+        assert_eq!(kept, sorted[sorted.len() - 10..].to_vec());
+        let kept: Vec<_> = smallest.iter().cloned().collect();
+        assert_eq!(kept, sorted[..10].to_vec());
+    }
+}
@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+
+use crate::keyed_skiplist::KeyedSkipList;
+use crate::level_generator::LevelGenerator;
+
+/// A queue of items ordered by their deadlines.
+///
+/// Items with the same deadline are popped in the order they are pushed.
+pub struct DelayQueue<D: Ord, T> {
+    sk: KeyedSkipList<D, (D, T)>,
+}
+
+impl<D: Ord, T> DelayQueue<D, T> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::new())
+    }
+
+    pub fn with_level_generator(lg: LevelGenerator) -> Self {
+        DelayQueue {
+            sk: KeyedSkipList::with_config(true, lg, |entry| &entry.0),
+        }
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Push an item which is due at the deadline
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::delay_queue::DelayQueue;
+    ///
+    /// let mut dq = DelayQueue::new();
+    /// dq.push(30, "c");
+    /// dq.push(10, "a");
+    /// dq.push(20, "b");
+    /// assert_eq!(dq.peek_next_deadline(), Some(&10));
+    /// ```
+    pub fn push(&mut self, deadline: D, item: T) {
+        // Treat equal deadlines as less, so the item is put after the items
+        // with the same deadline.
+        self.sk.sk._insert_by((deadline, item), true, |a, b| match a.0.cmp(&b.0) {
+            Ordering::Equal => Ordering::Less,
+            ord => ord,
+        });
+    }
+
+    /// Returns the nearest deadline
+    pub fn peek_next_deadline(&self) -> Option<&D> {
+        self.sk.front().map(|entry| &entry.0)
+    }
+
+    /// Returns the item with the nearest deadline
+    pub fn peek(&self) -> Option<(&D, &T)> {
+        self.sk.front().map(|entry| (&entry.0, &entry.1))
+    }
+
+    /// Pop the item with the nearest deadline whether it's due or not
+    pub fn pop(&mut self) -> Option<(D, T)> {
+        self.sk.pop_front()
+    }
+
+    /// Pop all items whose deadlines are not later than `now`, they are unlinked
+    /// from the queue in one splice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::delay_queue::DelayQueue;
+    ///
+    /// let mut dq = DelayQueue::new();
+    /// for i in 0..10 {
+    ///     dq.push(i, i * 10);
+    /// }
+    ///
+    /// let expired = dq.pop_expired(&3);
+    /// assert_eq!(expired, vec![(0, 0), (1, 10), (2, 20), (3, 30)]);
+    /// assert_eq!(dq.len(), 6);
+    /// ```
+    pub fn pop_expired(&mut self, now: &D) -> Vec<(D, T)> {
+        let (_, count) = self.sk.sk._seek_by(|entry| entry.0.cmp(now), true);
+        let mut result = Vec::with_capacity(count);
+        let mut detached = self.sk.sk._unlink_range(0, count);
+        while let Some(mut node) = detached {
+            detached = node.next.take();
+            result.push(node.value.take().expect("normal node always has a value"));
+        }
+        result
+    }
+
+    /// Returns number of items in the queue
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn equal_deadlines_in_push_order() {
+        let mut dq = DelayQueue::new();
+        dq.push(2, "x");
+        dq.push(1, "a");
+        dq.push(1, "b");
+        dq.push(3, "y");
+        dq.push(1, "c");
+
+        assert_eq!(dq.pop_expired(&0), vec![]);
+        assert_eq!(dq.pop_expired(&1), vec![(1, "a"), (1, "b"), (1, "c")]);
+        assert_eq!(dq.peek(), Some((&2, &"x")));
+        assert_eq!(dq.pop_expired(&5), vec![(2, "x"), (3, "y")]);
+        assert_eq!(dq.len(), 0);
+        assert_eq!(dq.pop(), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::borrow::Borrow;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+
+use crate::ordered_skiplist::OrderedSkipList;
+use crate::skiplist::{Iter, Range, SkipList};
+
+/// An immutable skiplist that can be shared across threads.
+///
+/// It's created by [`SkipList::freeze`] or [`OrderedSkipList::freeze`], the
+/// nodes are kept in an `Arc`, so cloning it is cheap.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::ordered_skiplist::OrderedSkipList;
+/// use std::thread;
+///
+/// let mut sk = OrderedSkipList::new();
+/// for i in 0..10 {
+///     sk.insert(i * 10);
+/// }
+/// let frozen = sk.freeze();
+///
+/// let handles: Vec<_> = (0..2)
+///     .map(|i| {
+///         let frozen = frozen.clone();
+///         thread::spawn(move || frozen.rank(&(i * 50)))
+///     })
+///     .collect();
+/// let ranks: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+/// assert_eq!(ranks, vec![0, 5]);
+/// ```
+pub struct FrozenSkipList<V> {
+    sk: Arc<SkipList<V>>,
+}
+
+impl<V> Clone for FrozenSkipList<V> {
+    fn clone(&self) -> Self {
+        FrozenSkipList {
+            sk: self.sk.clone(),
+        }
+    }
+}
+
+impl<V> FrozenSkipList<V> {
+    /// Returns length of the skiplist
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Returns the first value
+    pub fn front(&self) -> Option<&V> {
+        self.sk.front()
+    }
+
+    /// Returns the last value
+    pub fn back(&self) -> Option<&V> {
+        self.sk.back()
+    }
+
+    /// Returns an iterator of the skiplist
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.sk.iter()
+    }
+
+    /// Returns a range iterator by index
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    pub fn range<R>(&self, range: R) -> Range<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.sk.range(range)
+    }
+
+    /// Returns number of values less than q, which is the index q would be
+    /// inserted at. Values should be sorted, e.g. frozen from an `OrderedSkipList`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(1);
+    /// sk.insert(3);
+    /// let frozen = sk.freeze();
+    /// assert_eq!(frozen.rank(&0), 0);
+    /// assert_eq!(frozen.rank(&3), 1);
+    /// assert_eq!(frozen.rank(&4), 2);
+    /// ```
+    pub fn rank<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk._seek_by(|v| v.borrow().cmp(q), false).1
+    }
+}
+
+impl<V: std::fmt::Debug> std::fmt::Debug for FrozenSkipList<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.sk.fmt(f)
+    }
+}
+
+impl<V> SkipList<V> {
+    /// Freeze the skiplist into an immutable one that is cheap to clone and can
+    /// be shared across threads
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// let frozen = sk.freeze();
+    /// let shared = frozen.clone();
+    /// assert_eq!(shared.get(0), Some(&0));
+    /// ```
+    pub fn freeze(mut self) -> FrozenSkipList<V> {
+        // the finger is shared state behind a lock, reads of a frozen list
+        // shouldn't contend on it
+        self.set_finger(false);
+        FrozenSkipList { sk: Arc::new(self) }
+    }
+}
+
+impl<V: Ord> OrderedSkipList<V> {
+    /// Freeze the ordered skiplist into an immutable one that is cheap to clone
+    /// and can be shared across threads
+    pub fn freeze(self) -> FrozenSkipList<V> {
+        self.sk.freeze()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn freeze_drops_finger() {
+        let mut sk = SkipList::new();
+        sk.set_finger(true);
+        sk.extend(0..100);
+        assert_eq!(sk.get(50), Some(&50));
+
+        let frozen = sk.freeze();
+        assert!(!frozen.sk.has_finger());
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let shared = frozen.clone();
+                std::thread::spawn(move || {
+                    (0..100).all(|i| shared.get((i * 7 + t) % 100) == Some(&((i * 7 + t) % 100)))
+                })
+            })
+            .collect();
+        assert!(handles.into_iter().all(|h| h.join().unwrap()));
+    }
+}
@@ -0,0 +1,401 @@
+use crate::skiplist::IntoIter;
+use crate::skiplist::Iter;
+use crate::skiplist::Range;
+use crate::skiplist::ReverseIter;
+use crate::skiplist::ReverseRange;
+use std::borrow::Borrow;
+use std::ops::{Bound, RangeBounds};
+
+use crate::level_generator::LevelGenerator;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::skiplist::SkipList;
+use crate::value_range::ValueRange;
+
+/// An ordered skiplist whose elements are ordered by a key projected from them.
+///
+/// The key extractor is given at construction, so values don't have to implement
+/// `Ord`, only the projected key does.
+pub struct KeyedSkipList<K: Ord, V> {
+    pub(crate) sk: SkipList<V>,
+    key: fn(&V) -> &K,
+    duplicatable: bool,
+}
+
+impl<K: Ord, V> KeyedSkipList<K, V> {
+    /// Create a keyed skiplist that doesn't allow elements with equal keys
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::keyed_skiplist::KeyedSkipList;
+    ///
+    /// struct Job {
+    ///     priority: u32,
+    ///     name: &'static str,
+    /// }
+    ///
+    /// let mut sk = KeyedSkipList::new(|job: &Job| &job.priority);
+    /// sk.insert(Job { priority: 2, name: "b" });
+    /// sk.insert(Job { priority: 1, name: "a" });
+    /// assert_eq!(sk.get(0).map(|job| job.name), Some("a"));
+    /// ```
+    pub fn new(key: fn(&V) -> &K) -> Self {
+        Self::with_config(false, LevelGenerator::new(), key)
+    }
+
+    /// Create a keyed skiplist that allows elements with equal keys
+    pub fn new_duplicatable(key: fn(&V) -> &K) -> Self {
+        Self::with_config(true, LevelGenerator::new(), key)
+    }
+
+    pub fn with_config(dup: bool, lg: LevelGenerator, key: fn(&V) -> &K) -> Self {
+        Self {
+            sk: SkipList::with_level_generator(lg),
+            key,
+            duplicatable: dup,
+        }
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_level_config(dup: bool, p: f64, max_levels: usize, key: fn(&V) -> &K) -> Self {
+        Self::with_config(dup, LevelGenerator::with_config(p, max_levels), key)
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Choose the levels of all elements again, see
+    /// [`SkipList::rebuild`](../skiplist/struct.SkipList.html#method.rebuild)
+    pub fn rebuild(&mut self) {
+        self.sk.rebuild()
+    }
+
+    /// Returns numbers of operations since the keyed skiplist is created or its
+    /// metrics are reset
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.sk.metrics()
+    }
+
+    /// Reset all metrics to 0
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&self) {
+        self.sk.reset_metrics()
+    }
+
+    /// Returns the key of a value, which is projected by the extractor
+    /// given at construction
+    pub fn key_of<'v>(&self, value: &'v V) -> &'v K {
+        (self.key)(value)
+    }
+
+    /// Returns length of the keyed skiplist
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Insert value by its key, if the keyed skiplist is duplicatable return None
+    /// after inserted, if it's not duplicatable and the key exists, the old value
+    /// is replaced and returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::keyed_skiplist::KeyedSkipList;
+    ///
+    /// let mut sk = KeyedSkipList::new(|pair: &(i32, &str)| &pair.0);
+    /// sk.insert((1, "a"));
+    /// sk.insert((0, "b"));
+    /// assert_eq!(sk.insert((1, "c")), Some((1, "a")));
+    /// assert_eq!(sk.get(1), Some(&(1, "c")));
+    /// ```
+    pub fn insert(&mut self, value: V) -> Option<V> {
+        let key = self.key;
+        self.sk
+            ._insert_by(value, self.duplicatable, |a, b| key(a).cmp(key(b)))
+            .1
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Get the first element whose key equals to q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::keyed_skiplist::KeyedSkipList;
+    ///
+    /// let mut sk = KeyedSkipList::new_duplicatable(|pair: &(i32, &str)| &pair.0);
+    /// sk.insert((1, "a"));
+    /// sk.insert((0, "b"));
+    /// sk.insert((1, "c"));
+    /// assert_eq!(sk.get_first(&1), Some((1, &(1, "c"))));
+    /// assert_eq!(sk.get_first(&2), None);
+    /// ```
+    pub fn get_first<Q: ?Sized>(&self, q: &Q) -> Option<(usize, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let key = self.key;
+        self.sk._get_first_by(|v| key(v).borrow().cmp(q))
+    }
+
+    /// Get the last element whose key equals to q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::keyed_skiplist::KeyedSkipList;
+    ///
+    /// let mut sk = KeyedSkipList::new_duplicatable(|pair: &(i32, &str)| &pair.0);
+    /// sk.insert((1, "a"));
+    /// sk.insert((0, "b"));
+    /// sk.insert((1, "c"));
+    /// assert_eq!(sk.get_last(&1), Some((2, &(1, "a"))));
+    /// ```
+    pub fn get_last<Q: ?Sized>(&self, q: &Q) -> Option<(usize, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let key = self.key;
+        self.sk._get_last_by(|v| key(v).borrow().cmp(q))
+    }
+
+    /// Check if there is an element whose key equals to q
+    pub fn contains_key<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_first(q).is_some()
+    }
+
+    /// Remove item at the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    ///
+    pub fn remove(&mut self, index: usize) -> V {
+        self.sk.remove(index)
+    }
+
+    /// Remove the first item whose key equals to q, returns the removed value
+    pub fn remove_first<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let index = self.get_first(q)?.0;
+        Some(self.remove(index))
+    }
+
+    /// Remove the last item whose key equals to q, returns the removed value
+    pub fn remove_last<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let index = self.get_last(q)?.0;
+        Some(self.remove(index))
+    }
+
+    /// Remove all items whose key equals to q, returns number of items removed
+    pub fn remove_key<Q: ?Sized>(&mut self, q: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self._key_range_to_index_range((Bound::Included(q), Bound::Included(q)));
+        self.sk.remove_range(left..right)
+    }
+
+    /// Returns the first value in the keyed skiplist
+    pub fn front(&self) -> Option<&V> {
+        self.sk.front()
+    }
+
+    /// Returns the last value in the keyed skiplist
+    pub fn back(&self) -> Option<&V> {
+        self.sk.back()
+    }
+
+    /// Pop the first value in the keyed skiplist
+    pub fn pop_front(&mut self) -> Option<V> {
+        self.sk.pop_front()
+    }
+
+    /// Pop the last value in the keyed skiplist
+    pub fn pop_back(&mut self) -> Option<V> {
+        self.sk.pop_back()
+    }
+
+    /// Returns an iterator for the keyed skiplist
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.sk.iter()
+    }
+
+    /// Returns a reverse iterator for the keyed skiplist
+    pub fn reverse_iter(&self) -> ReverseIter<'_, V> {
+        self.sk.reverse_iter()
+    }
+
+    /// Returns a range iterator of elements whose keys are in the range
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::keyed_skiplist::KeyedSkipList;
+    ///
+    /// let mut sk = KeyedSkipList::new(|pair: &(i32, i32)| &pair.0);
+    /// for i in 0..10 {
+    ///     sk.insert((i, i * 10));
+    /// }
+    ///
+    /// let values: Vec<_> = sk.range(&2..&5).map(|pair| pair.1).collect();
+    /// assert_eq!(values, vec![20, 30, 40]);
+    /// ```
+    pub fn range<R, Q: ?Sized>(&self, range: R) -> Range<'_, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self._key_range_to_index_range(range);
+        self.sk.range(left..right)
+    }
+
+    /// Returns a reverse range iterator of elements whose keys are in the range
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    pub fn reverse_range<R, Q: ?Sized>(&self, range: R) -> ReverseRange<'_, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self._key_range_to_index_range(range);
+        self.sk.reverse_range(left..right)
+    }
+
+    /// Returns whether any element has a key in the range, with one search
+    pub fn any_in_range<R, Q: ?Sized>(&self, range: R) -> bool
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let key = self.key;
+        self.sk
+            ._any_in_range_by(range.start_bound(), range.end_bound(), |v, q| {
+                key(v).borrow().cmp(q)
+            })
+    }
+
+    pub(crate) fn _key_range_to_index_range<R, Q: ?Sized>(&self, range: R) -> (usize, usize)
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let key = self.key;
+        let left = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(q) => self.sk._seek_by(|v| key(v).borrow().cmp(q), false).1,
+            Bound::Excluded(q) => self.sk._seek_by(|v| key(v).borrow().cmp(q), true).1,
+        };
+
+        let right = match range.end_bound() {
+            Bound::Unbounded => self.len(),
+            Bound::Included(q) => self.sk._seek_by(|v| key(v).borrow().cmp(q), true).1,
+            Bound::Excluded(q) => self.sk._seek_by(|v| key(v).borrow().cmp(q), false).1,
+        };
+
+        (left, right)
+    }
+
+    /// Returns graph that contains a range of elements of the skiplist
+    /// same as [`SkipList::explain`]: trait.SkipList.html#method.explain
+    pub fn explain<R>(&self, range: R) -> Result<String, &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+    {
+        self.sk.explain(range)
+    }
+
+    /// Write the graph of `explain` to the writer, same as
+    /// [`SkipList::explain_to`](../skiplist/struct.SkipList.html#method.explain_to)
+    pub fn explain_to<R, W>(&self, range: R, w: &mut W) -> Result<(), &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+        W: std::fmt::Write,
+    {
+        self.sk.explain_to(range, w)
+    }
+}
+
+impl<K: Ord, V> IntoIterator for KeyedSkipList<K, V> {
+    type Item = V;
+    type IntoIter = IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sk.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Player {
+        score: u32,
+        name: &'static str,
+    }
+
+    #[test]
+    fn keyed_insert_and_remove() {
+        let mut sk = KeyedSkipList::new_duplicatable(|p: &Player| &p.score);
+        sk.insert(Player { score: 30, name: "a" });
+        sk.insert(Player { score: 10, name: "b" });
+        sk.insert(Player { score: 20, name: "c" });
+        sk.insert(Player { score: 20, name: "d" });
+
+        let names: Vec<_> = sk.iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["b", "d", "c", "a"]);
+
+        assert_eq!(sk.get_last(&20).map(|(i, p)| (i, p.name)), Some((2, "c")));
+        assert_eq!(sk.remove_key(&20), 2);
+        assert_eq!(sk.len(), 2);
+        assert!(!sk.contains_key(&20));
+        assert_eq!(sk.remove_first(&10).map(|p| p.name), Some("b"));
+        assert_eq!(sk.front().map(|p| p.name), Some("a"));
+    }
+}
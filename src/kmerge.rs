@@ -0,0 +1,172 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::iter::FusedIterator;
+
+/// Merge any number of sorted iterators into one sorted iterator.
+///
+/// Every source should be sorted in ascending order. Equal elements are yielded
+/// in the order of their sources, so the merge is stable.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::kmerge::kmerge;
+/// use skiplist::skipset::SkipSet;
+///
+/// let mut shards = vec![SkipSet::new(), SkipSet::new(), SkipSet::new()];
+/// for i in 0..9 {
+///     shards[i % 3].add(i);
+/// }
+///
+/// let merged: Vec<_> = kmerge(shards.iter().map(|shard| shard.iter())).cloned().collect();
+/// assert_eq!(merged, (0..9).collect::<Vec<_>>());
+/// ```
+pub fn kmerge<I>(sources: I) -> KMerge<<I::Item as IntoIterator>::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: Ord,
+{
+    let mut iters: Vec<_> = sources.into_iter().map(|source| source.into_iter()).collect();
+    let mut heap = BinaryHeap::with_capacity(iters.len());
+    for (index, iter) in iters.iter_mut().enumerate() {
+        if let Some(value) = iter.next() {
+            heap.push(Reverse((value, index)));
+        }
+    }
+
+    KMerge { iters, heap }
+}
+
+/// A lazy iterator merging sorted iterators.
+///
+/// This `struct` is created by the [`kmerge`] function. See its documentation for more.
+///
+/// [`kmerge`]: fn.kmerge.html
+pub struct KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    iters: Vec<I>,
+    heap: BinaryHeap<Reverse<(I::Item, usize)>>,
+}
+
+impl<I> Clone for KMerge<I>
+where
+    I: Iterator + Clone,
+    I::Item: Ord + Clone,
+{
+    fn clone(&self) -> Self {
+        KMerge {
+            iters: self.iters.clone(),
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<I> std::fmt::Debug for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("KMerge")
+            .field("sources", &self.iters.len())
+            .field("heads", &self.heap)
+            .finish()
+    }
+}
+
+impl<I> Iterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, index)) = self.heap.pop()?;
+        if let Some(next) = self.iters[index].next() {
+            self.heap.push(Reverse((next, index)));
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // every source with a value in the heap has one more than it reports
+        let mut lower = self.heap.len();
+        let mut upper = Some(self.heap.len());
+        for iter in self.iters.iter() {
+            let (lo, hi) = iter.size_hint();
+            lower = lower.saturating_add(lo);
+            upper = match (upper, hi) {
+                (Some(upper), Some(hi)) => upper.checked_add(hi),
+                _ => None,
+            };
+        }
+        (lower, upper)
+    }
+}
+
+// Once the heap is empty nothing is pulled from the sources any more, so the
+// merge is fused even if the sources aren't.
+impl<I> FusedIterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kmerge_is_stable() {
+        let sources = vec![vec![(1, 'a'), (3, 'a')], vec![], vec![(1, 'c'), (2, 'c')]];
+        let merged: Vec<_> = kmerge(sources.iter().map(|source| {
+            source.iter().map(|&(k, src)| Wrapper(k, src))
+        }))
+        .map(|w| (w.0, w.1))
+        .collect();
+        assert_eq!(merged, vec![(1, 'a'), (1, 'c'), (2, 'c'), (3, 'a')]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let sources = vec![vec![1, 4], vec![], vec![2, 3, 5]];
+        let mut merged = kmerge(sources.iter().map(|source| source.iter()));
+        assert_eq!(merged.size_hint(), (5, Some(5)));
+        merged.next();
+        assert_eq!(merged.size_hint(), (4, Some(4)));
+
+        let filtered = kmerge(sources.iter().map(|source| source.iter().filter(|v| **v > 2)));
+        assert_eq!(filtered.size_hint(), (2, Some(3)));
+        assert_eq!(filtered.count(), 3);
+    }
+
+    // Compares by the first field only, to check ties follow source order
+    #[derive(Debug)]
+    struct Wrapper(i32, char);
+
+    impl PartialEq for Wrapper {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Wrapper {}
+
+    impl PartialOrd for Wrapper {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Wrapper {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+}
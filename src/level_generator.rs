@@ -23,7 +23,15 @@ impl LevelGenerator {
         Self::with_config(p, DEFAULT_LEVELS)
     }
 
+    /// Create a level generator, each level's probability is `p` of its previous
+    /// level, and there are at most `levels` levels
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `(0, 1)` or `levels` is 0
     pub fn with_config(p: f64, levels: usize) -> Self {
+        assert!(p > 0.0 && p < 1.0, "probability should be in (0, 1)");
+        assert!(levels > 0, "levels should be greater than 0");
         Self {
             p,
             levels,
@@ -62,6 +70,36 @@ impl LevelGenerator {
         level
     }
 
+    /// Returns the probability of a level relative to its previous level
+    pub fn probability(&self) -> f64 {
+        self.p
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Change the probability, only levels chosen afterwards are affected
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::level_generator::LevelGenerator;
+    ///
+    /// let mut lg = LevelGenerator::new();
+    /// lg.set_probability(0.25);
+    /// assert_eq!(lg.probability(), 0.25);
+    /// ```
+    pub fn set_probability(&mut self, p: f64) {
+        assert!(p > 0.0 && p < 1.0, "probability should be in (0, 1)");
+        self.p = p;
+    }
+
     pub fn shrink(&mut self) -> usize {
         if self.cur_level_limit > 0 {
             self.cur_level_limit -= 1;
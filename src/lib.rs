@@ -1,7 +1,25 @@
+#[macro_use]
+mod macros;
+
 pub mod level_generator;
 pub mod skiplist;
 pub mod ordered_skiplist;
+pub mod keyed_skiplist;
 pub mod skipset;
+pub mod skipmap;
+pub mod skipmultimap;
+pub mod delay_queue;
+pub mod priority_queue;
+pub mod kmerge;
+pub mod frozen_skiplist;
+pub mod bounded;
+pub mod run_skiplist;
+pub mod value_range;
+#[cfg(feature = "arena")]
+pub mod arena_skiplist;
+pub mod metrics;
+#[cfg(feature = "bloom")]
+mod bloom;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,16 @@
+/// Enter a debug span that lasts to the end of the current block, it's only
+/// emitted with the `tracing` feature.
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+/// Emit a debug event, it's only emitted with the `tracing` feature.
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "metrics")]
+use crate::level_generator::DEFAULT_LEVELS;
+
+/// Numbers of operations of a skiplist since it's created or its metrics are
+/// reset. They are only counted with the `metrics` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Number of inserted values
+    pub inserts: u64,
+    /// Number of removed values
+    pub removes: u64,
+    /// Number of searches by index or by value
+    pub searches: u64,
+    /// Number of nodes moved to while searching, inserting and removing
+    pub nodes_visited: u64,
+}
+
+/// Counters kept in a skiplist. Without the `metrics` feature it has no fields
+/// and counting does nothing.
+#[cfg(feature = "metrics")]
+#[derive(Default)]
+pub(crate) struct Counters {
+    inserts: AtomicU64,
+    removes: AtomicU64,
+    searches: AtomicU64,
+    nodes_visited: AtomicU64,
+    // nodes moved to in every level, moves above the last level are counted in it
+    level_visits: [AtomicU64; DEFAULT_LEVELS],
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct Counters;
+
+#[cfg(feature = "metrics")]
+impl Counters {
+    pub(crate) fn new() -> Self {
+        Counters::default()
+    }
+
+    pub(crate) fn insert(&self, n: usize) {
+        self.inserts.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn remove(&self, n: usize) {
+        self.removes.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn search(&self) {
+        self.searches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Take back the removal and the insertion counted for a node that's
+    /// unlinked and linked again in the same list, which only moves a value
+    pub(crate) fn uncount_move(&self) {
+        self.inserts.fetch_sub(1, Ordering::Relaxed);
+        self.removes.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn visit(&self, level: usize) {
+        self.nodes_visited.fetch_add(1, Ordering::Relaxed);
+        self.level_visits[level.min(DEFAULT_LEVELS - 1)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Metrics {
+        Metrics {
+            inserts: self.inserts.load(Ordering::Relaxed),
+            removes: self.removes.load(Ordering::Relaxed),
+            searches: self.searches.load(Ordering::Relaxed),
+            nodes_visited: self.nodes_visited.load(Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn level_visits(&self, levels: usize) -> Vec<u64> {
+        self.level_visits[..levels.min(DEFAULT_LEVELS)]
+            .iter()
+            .map(|visits| visits.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    pub(crate) fn reset(&self) {
+        self.inserts.store(0, Ordering::Relaxed);
+        self.removes.store(0, Ordering::Relaxed);
+        self.searches.store(0, Ordering::Relaxed);
+        self.nodes_visited.store(0, Ordering::Relaxed);
+        for visits in self.level_visits.iter() {
+            visits.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+impl Counters {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Counters
+    }
+
+    #[inline(always)]
+    pub(crate) fn insert(&self, _n: usize) {}
+
+    #[inline(always)]
+    pub(crate) fn remove(&self, _n: usize) {}
+
+    #[inline(always)]
+    pub(crate) fn search(&self) {}
+
+    #[inline(always)]
+    pub(crate) fn uncount_move(&self) {}
+
+    #[inline(always)]
+    pub(crate) fn visit(&self, _level: usize) {}
+}
@@ -1,18 +1,40 @@
 use crate::skiplist::IntoIter;
 use crate::skiplist::Iter;
+use crate::skiplist::IterMut;
 use crate::skiplist::Range;
+use crate::skiplist::RangeMut;
 use crate::skiplist::ReverseIter;
 use crate::skiplist::ReverseRange;
+use crate::skiplist::View;
 use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::iter::FusedIterator;
 use std::ops::{Bound, RangeBounds};
 
 use crate::level_generator::LevelGenerator;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
 use crate::skiplist::{Node, SkipList};
+use crate::skipmap::SkipMap;
+use crate::value_range::ValueRange;
+
+/// An element ordered by a key part of it, the other part is a payload that can
+/// be changed in place, see [`OrderedSkipList::iter_mut`].
+///
+/// The order of the element must only depend on the key, the ordered skiplist
+/// is broken if changing the payload changes the order.
+pub trait KeyedValue {
+    type Key: ?Sized;
+    type Payload: ?Sized;
+
+    /// Returns the key and the mutable payload
+    fn split_mut(&mut self) -> (&Self::Key, &mut Self::Payload);
+}
 
 pub struct OrderedSkipList<V: Ord> {
     pub(crate) sk: SkipList<V>,
-    duplicatable: bool,
+    pub(crate) duplicatable: bool,
 }
 
 impl<V: Ord> OrderedSkipList<V> {
@@ -31,6 +53,98 @@ impl<V: Ord> OrderedSkipList<V> {
         }
     }
 
+    /// Create an ordered skiplist from values sorted in ascending order without
+    /// duplicates, the values are linked in one splice without being compared.
+    ///
+    /// The order is only verified in debug builds, the ordered skiplist is broken
+    /// if the values are not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let sk = OrderedSkipList::from_sorted_vec_unchecked(vec![1, 3, 5]);
+    /// assert_eq!(sk.get_first(&3), Some((1, &3)));
+    /// ```
+    pub fn from_sorted_vec_unchecked(v: Vec<V>) -> Self {
+        let mut sk = Self::new();
+        sk.sk.extend_front(v);
+        debug_assert!(
+            sk.iter().zip(sk.iter().skip(1)).all(|(a, b)| a < b),
+            "values should be sorted without duplicates"
+        );
+        sk
+    }
+
+    /// Move the values into a `BinaryHeap`, the heap is built from the sorted
+    /// values in O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(2);
+    /// sk.insert(5);
+    /// sk.insert(1);
+    /// let mut heap = sk.into_binary_heap();
+    /// assert_eq!(heap.pop(), Some(5));
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn into_binary_heap(self) -> BinaryHeap<V> {
+        BinaryHeap::from(self.into_iter().collect::<Vec<_>>())
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let sk: OrderedSkipList<i64> = OrderedSkipList::with_level_config(true, 0.25, 16);
+    /// assert_eq!(sk.max_levels(), 16);
+    /// ```
+    pub fn with_level_config(dup: bool, p: f64, max_levels: usize) -> Self {
+        Self::with_config(dup, LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Choose the levels of all elements again, see
+    /// [`SkipList::rebuild`](../skiplist/struct.SkipList.html#method.rebuild)
+    pub fn rebuild(&mut self) {
+        self.sk.rebuild()
+    }
+
+    /// Returns numbers of operations since the ordered skiplist is created or its
+    /// metrics are reset
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.sk.metrics()
+    }
+
+    /// Reset all metrics to 0
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&self) {
+        self.sk.reset_metrics()
+    }
+
     /// Removes duplicated items
     ///
     /// # Examples
@@ -59,6 +173,77 @@ impl<V: Ord> OrderedSkipList<V> {
         self.sk.dedup();
     }
 
+    /// Group elements by the key extracted from them, elements in a group keep
+    /// their order in the ordered skiplist.
+    ///
+    /// If keys never decrease along the elements, which is the case when the key
+    /// is a prefix or a coarser version of the element, groups are built in one
+    /// pass and linked without being compared. Otherwise every element is
+    /// looked up in the groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in [35, 12, 31, 18, 40].iter() {
+    ///     sk.insert(*i);
+    /// }
+    ///
+    /// let groups = sk.group_by_key(|v| v / 10);
+    /// assert_eq!(groups.get(&1), Some(&vec![12, 18]));
+    /// assert_eq!(groups.get(&3), Some(&vec![31, 35]));
+    /// assert_eq!(groups.get(&4), Some(&vec![40]));
+    /// ```
+    pub fn group_by_key<K, F>(self, mut f: F) -> SkipMap<K, Vec<V>>
+    where
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        let mut sorted: Vec<(K, Vec<V>)> = vec![];
+        let mut iter = self.into_iter();
+        for value in iter.by_ref() {
+            let key = f(&value);
+            match sorted.last_mut() {
+                Some((last, group)) if *last == key => group.push(value),
+                Some((last, _)) if *last > key => {
+                    // keys are not monotone, look up the rest one by one
+                    let mut groups = SkipMap::from_sorted_iter_unchecked(sorted);
+                    let rest = iter.map(|v| (f(&v), v));
+                    SkipList::_group_into(&mut groups, std::iter::once((key, value)).chain(rest));
+                    return groups;
+                }
+                _ => sorted.push((key, vec![value])),
+            }
+        }
+        SkipMap::from_sorted_iter_unchecked(sorted)
+    }
+
+    /// Removes duplicated items, returns how many times each item occurs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// for i in [3, 1, 3, 2, 3].iter() {
+    ///     sk.insert(*i);
+    /// }
+    ///
+    /// let histogram = sk.dedup_counted();
+    /// assert_eq!(sk.len(), 3);
+    /// let counts: Vec<_> = histogram.iter().map(|(v, n)| (*v, *n)).collect();
+    /// assert_eq!(counts, vec![(1, 1), (2, 1), (3, 3)]);
+    /// ```
+    pub fn dedup_counted(&mut self) -> SkipMap<V, usize>
+    where
+        V: Clone,
+    {
+        self.sk.dedup_counted()
+    }
+
     /// Returns length of the ordered_skiplist
     pub fn len(&self) -> usize {
         self.sk.len()
@@ -131,9 +316,9 @@ impl<V: Ord> OrderedSkipList<V> {
     /// }
     /// assert_eq!(i, 7);
     /// ```
-    pub fn range<'a, 'b, R, Q: 'b + ?Sized>(&'a self, range: R) -> Range<'a, V>
+    pub fn range<R, Q: ?Sized>(&self, range: R) -> Range<'_, V>
     where
-        R: RangeBounds<&'b Q>,
+        R: ValueRange<Q>,
         V: Borrow<Q>,
         Q: Ord,
     {
@@ -141,18 +326,7 @@ impl<V: Ord> OrderedSkipList<V> {
             return self.sk.range(0..0);
         }
 
-        let left = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(q) => self._index_not_less(q),
-            Bound::Excluded(q) => self._index_not_less_or_equal(q),
-        };
-
-        let right = match range.end_bound() {
-            Bound::Unbounded => self.len(),
-            Bound::Included(q) => self._index_not_less_or_equal(q),
-            Bound::Excluded(q) => self._index_not_less(q),
-        };
-
+        let (left, right) = self._value_range_to_index_range(range);
         self.sk.range(left..right)
     }
 
@@ -179,9 +353,9 @@ impl<V: Ord> OrderedSkipList<V> {
     /// }
     /// assert_eq!(i, 1);
     /// ```
-    pub fn reverse_range<'a, 'b, R, Q: 'b + ?Sized>(&'a self, range: R) -> ReverseRange<'a, V>
+    pub fn reverse_range<R, Q: ?Sized>(&self, range: R) -> ReverseRange<'_, V>
     where
-        R: RangeBounds<&'b Q>,
+        R: ValueRange<Q>,
         V: Borrow<Q>,
         Q: Ord,
     {
@@ -189,224 +363,418 @@ impl<V: Ord> OrderedSkipList<V> {
             return self.sk.reverse_range(0..0);
         }
 
-        let left = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(q) => self._index_not_less(q),
-            Bound::Excluded(q) => self._index_not_less_or_equal(q),
-        };
-
-        let right = match range.end_bound() {
-            Bound::Unbounded => self.len(),
-            Bound::Included(q) => self._index_not_less_or_equal(q),
-            Bound::Excluded(q) => self._index_not_less(q),
-        };
-
+        let (left, right) = self._value_range_to_index_range(range);
         self.sk.reverse_range(left..right)
     }
 
-    fn _index_not_less<Q: ?Sized>(&self, q: &Q) -> usize
+    /// Returns a view of elements in the value range, the range is converted to
+    /// indexes once and the view can be iterated, counted and indexed many times
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i * 10);
+    /// }
+    ///
+    /// let window = sk.view_by_value(25..=60);
+    /// assert_eq!(window.len(), 4);
+    /// assert_eq!(window.offset(), 3);
+    /// assert_eq!(window.first(), Some(&30));
+    /// assert_eq!(window.iter().cloned().collect::<Vec<_>>(), vec![30, 40, 50, 60]);
+    /// ```
+    pub fn view_by_value<R, Q: ?Sized>(&self, range: R) -> View<'_, V>
     where
+        R: ValueRange<Q>,
         V: Borrow<Q>,
         Q: Ord,
     {
         if self.len() == 0 {
-            panic!("Can't get index from empty skiplist.");
-        }
-        let mut cur_index = 0;
-        let mut cur_level = self.sk.head.links.len() - 1;
-        let mut cur_ptr: *const _ = &*self.sk.head;
-
-        loop {
-            // Safety: cur_ptr will never be null and always valid.
-            let next_ptr = unsafe { (*cur_ptr).links[cur_level] };
-            if next_ptr.is_null() {
-                if cur_level == 0 {
-                    break;
-                }
-                cur_level -= 1;
-                continue;
-            }
-
-            // Safety: next_ptr will not be null when the program run to here.
-            let next_value = unsafe {
-                (*next_ptr)
-                    .value
-                    .as_ref()
-                    .expect("there must be value in a normal node")
-            };
-            match q.cmp(next_value.borrow()) {
-                Ordering::Greater => {
-                    // Safety: cur_ptr will never be null and always valid.
-                    cur_index += unsafe { (*cur_ptr).links_len[cur_level] };
-                    cur_ptr = next_ptr;
-                    continue;
-                }
-                _ => (),
-            }
-            if cur_level == 0 {
-                break;
-            }
-            cur_level -= 1;
+            return self.sk.view(0..0);
         }
 
-        // cur_index is prev node index plus 1, so cur_index is index of item not less than q
-        cur_index
+        let (left, right) = self._value_range_to_index_range(range);
+        self.sk.view(left..right)
     }
 
-    fn _index_not_less_or_equal<Q: ?Sized>(&self, q: &Q) -> usize
+    /// Returns an ordered skiplist with clones of the elements in the range, the
+    /// elements are cloned in one traversal and linked in one splice
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i);
+    /// }
+    ///
+    /// let part = sk.clone_range(&3..&6);
+    /// assert_eq!(part.iter().cloned().collect::<Vec<_>>(), vec![3, 4, 5]);
+    /// ```
+    pub fn clone_range<R, Q: ?Sized>(&self, range: R) -> OrderedSkipList<V>
     where
-        V: Borrow<Q>,
+        R: ValueRange<Q>,
+        V: Borrow<Q> + Clone,
         Q: Ord,
     {
-        if self.len() == 0 {
-            panic!("Can't get index from empty skiplist.");
-        }
-        let mut cur_index = 0;
-        let mut cur_level = self.sk.head.links.len() - 1;
-        let mut cur_ptr: *const _ = &*self.sk.head;
-
-        loop {
-            // Safety: cur_ptr will never be null and always valid.
-            let next_ptr = unsafe { (*cur_ptr).links[cur_level] };
-            if next_ptr.is_null() {
-                if cur_level == 0 {
-                    break;
-                }
-                cur_level -= 1;
-                continue;
-            }
-
-            // Safety: next_ptr will not be null when the program run to here.
-            let next_value = unsafe {
-                (*next_ptr)
-                    .value
-                    .as_ref()
-                    .expect("there must be value in a normal node")
-            };
-            match q.cmp(next_value.borrow()) {
-                Ordering::Less => (),
-                _ => {
-                    // Safety: cur_ptr will never be null and always valid.
-                    cur_index += unsafe { (*cur_ptr).links_len[cur_level] };
-                    cur_ptr = next_ptr;
-                    continue;
-                }
-            }
-            if cur_level == 0 {
-                break;
-            }
-            cur_level -= 1;
-        }
-
-        cur_index
+        let mut result = Self::with_config(self.duplicatable, self.sk.level_generator.clone());
+        result.sk._insert_iter_at(0, self.range(range).cloned());
+        result
     }
 
-    /// Returns value at the given index, or `None` if the index is out of bounds
+    /// Returns whether any element is in the range, with one search and without
+    /// creating an iterator
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```
     /// use skiplist::ordered_skiplist::OrderedSkipList;
     ///
     /// let mut sk = OrderedSkipList::new();
-    /// sk.insert(0);
-    /// sk.insert(1);
-    /// assert_eq!(sk.get(1), Some(&1));
+    /// sk.insert(10);
+    /// sk.insert(20);
+    ///
+    /// assert!(sk.any_in_range(&15..=&20));
+    /// assert!(!sk.any_in_range(&11..&20));
+    /// assert!(!sk.any_in_range(&21..));
     /// ```
-    pub fn get(&self, index: usize) -> Option<&V> {
-        self.sk.get(index)
+    pub fn any_in_range<R, Q: ?Sized>(&self, range: R) -> bool
+    where
+        R: ValueRange<Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk
+            ._any_in_range_by(range.start_bound(), range.end_bound(), |v, q| {
+                v.borrow().cmp(q)
+            })
     }
 
-    /// Get the last element equals to q
+    /// Returns an iterator of at most `before` elements less than q, the elements
+    /// equal to q, and at most `after` elements greater than q
     ///
     /// # Examples
     ///
     /// ```
     /// use skiplist::ordered_skiplist::OrderedSkipList;
     ///
-    /// let mut sk = OrderedSkipList::new_duplicatable();
-    /// sk.insert(1);
-    /// sk.insert(0);
-    /// sk.insert(1);
-    /// sk.insert(2);
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i * 10);
+    /// }
     ///
-    /// assert_eq!(sk.get_last(&2), Some((3, &2)));
-    /// assert_eq!(sk.get_last(&1), Some((2, &1)));
-    /// assert_eq!(sk.get_last(&3), None);
+    /// let around: Vec<_> = sk.neighbors(&50, 2, 1).cloned().collect();
+    /// assert_eq!(around, vec![30, 40, 50, 60]);
+    /// let around: Vec<_> = sk.neighbors(&55, 1, 5).cloned().collect();
+    /// assert_eq!(around, vec![50, 60, 70, 80, 90]);
     /// ```
-    pub fn get_last<Q: ?Sized>(&self, q: &Q) -> Option<(usize, &V)>
+    pub fn neighbors<Q: ?Sized>(&self, q: &Q, before: usize, after: usize) -> Range<'_, V>
     where
         V: Borrow<Q>,
         Q: Ord,
     {
-        if self.len() == 0 {
-            return None;
-        }
-        let sk = &self.sk;
-        let mut cur_level = sk.head.links.len() - 1;
-        let mut cur_index = 0;
-        let mut cur_ptr: *const _ = &*sk.head;
-        let mut has_equal = false;
-
-        loop {
-            // Safety: cur_ptr will never be null and always valid.
-            let cur_node = unsafe { &*cur_ptr };
-            let next_ptr = cur_node.links[cur_level];
-            if next_ptr.is_null() {
-                if cur_level == 0 {
-                    break;
-                }
-                cur_level -= 1;
-                continue;
-            }
-
-            // Safety: next_ptr will not be null when the program run to here
-            let next_value = unsafe {
-                (*next_ptr)
-                    .value
-                    .as_ref()
-                    .expect("there must be value in a normal node")
-            };
-            match next_value.borrow().cmp(q) {
-                Ordering::Less => {
-                    cur_ptr = next_ptr;
-                    cur_index += cur_node.links_len[cur_level];
-                    continue;
-                }
-                Ordering::Equal => {
-                    has_equal = true;
-                    cur_ptr = cur_node.links[cur_level];
-                    cur_index += cur_node.links_len[cur_level];
-                    continue;
-                }
-                Ordering::Greater => (),
-            }
-
-            if cur_level == 0 {
-                break;
-            }
-            cur_level -= 1;
-        }
-
-        if !has_equal {
-            return None;
-        }
-
-        // Safety: cur_ptr will never be null and always valid.
-        let v = unsafe { (*cur_ptr).value.as_ref() };
-
-        // cur_index is node index added by 1
-        v.map(|v| (cur_index - 1, v))
+        let left = self._index_not_less(q);
+        let right = self._index_not_less_or_equal(q);
+        self.sk.range(
+            left.saturating_sub(before)..right.saturating_add(after).min(self.len()),
+        )
     }
 
-    /// Get the first element equals to q
+    /// Returns an iterator of the element at the index with at most `before`
+    /// elements before it and at most `after` elements after it. It yields
+    /// nothing if the index is out of bounds.
     ///
     /// # Examples
     ///
     /// ```
     /// use skiplist::ordered_skiplist::OrderedSkipList;
     ///
-    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i * 10);
+    /// }
+    ///
+    /// let around: Vec<_> = sk.neighbors_of_rank(1, 5, 1).cloned().collect();
+    /// assert_eq!(around, vec![0, 10, 20]);
+    /// ```
+    pub fn neighbors_of_rank(&self, index: usize, before: usize, after: usize) -> Range<'_, V> {
+        if index >= self.len() {
+            return self.sk.range(0..0);
+        }
+        let right = index.saturating_add(after).saturating_add(1).min(self.len());
+        self.sk.range(index.saturating_sub(before)..right)
+    }
+
+    /// Returns an iterator of the `n` smallest elements, or all elements if
+    /// there are fewer than `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in [4, 2, 0, 3, 1].iter() {
+    ///     sk.insert(*i);
+    /// }
+    /// assert_eq!(sk.first_n(2).cloned().collect::<Vec<_>>(), vec![0, 1]);
+    /// assert_eq!(sk.last_n(2).cloned().collect::<Vec<_>>(), vec![3, 4]);
+    /// assert_eq!(sk.last_n(10).count(), 5);
+    /// ```
+    pub fn first_n(&self, n: usize) -> Range<'_, V> {
+        self.sk.first_n(n)
+    }
+
+    /// Returns an iterator of the `n` largest elements in ascending order, or
+    /// all elements if there are fewer than `n`
+    pub fn last_n(&self, n: usize) -> Range<'_, V> {
+        self.sk.last_n(n)
+    }
+
+    /// Returns at most `limit` elements starting from the index `offset`, and the
+    /// last of them as the continuation token if more elements follow
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i);
+    /// }
+    ///
+    /// assert_eq!(sk.page(4, 3), (vec![&4, &5, &6], Some(&6)));
+    /// assert_eq!(sk.page(8, 3), (vec![&8, &9], None));
+    /// assert_eq!(sk.page_after(&6, 2), (vec![&7, &8], Some(&8)));
+    /// ```
+    pub fn page(&self, offset: usize, limit: usize) -> (Vec<&V>, Option<&V>) {
+        self._page(offset, limit)
+    }
+
+    /// Returns at most `limit` elements greater than q, and the last of them as
+    /// the continuation token if more elements follow. All elements equal to q
+    /// are skipped, use [`OrderedSkipList::page`] to paginate duplicated
+    /// elements.
+    pub fn page_after<Q: ?Sized>(&self, q: &Q, limit: usize) -> (Vec<&V>, Option<&V>)
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self._page(self._index_not_less_or_equal(q), limit)
+    }
+
+    fn _page(&self, offset: usize, limit: usize) -> (Vec<&V>, Option<&V>) {
+        let left = offset.min(self.len());
+        let right = offset.saturating_add(limit).min(self.len());
+        let items: Vec<&V> = self.sk.range(left..right).collect();
+        let next = if right < self.len() {
+            items.last().copied()
+        } else {
+            None
+        };
+        (items, next)
+    }
+
+    /// Returns a lazy iterator producing elements of both ordered skiplists in order,
+    /// duplicated elements are all kept, and elements of `self` go first if equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut frozen = OrderedSkipList::new();
+    /// let mut active = OrderedSkipList::new();
+    /// for i in 0..5 {
+    ///     frozen.insert(i * 2);
+    ///     active.insert(i * 3);
+    /// }
+    ///
+    /// let merged: Vec<_> = frozen.merge_iter(&active).cloned().collect();
+    /// assert_eq!(merged, vec![0, 0, 2, 3, 4, 6, 6, 8, 9, 12]);
+    /// ```
+    pub fn merge_iter<'a>(&'a self, other: &'a OrderedSkipList<V>) -> MergeIter<'a, V> {
+        let mut lhs_iter = self.iter();
+        let mut rhs_iter = other.iter();
+        MergeIter {
+            lhs_value: lhs_iter.next(),
+            rhs_value: rhs_iter.next(),
+            lhs_iter,
+            rhs_iter,
+        }
+    }
+
+    /// Returns a lazy iterator producing elements of both ordered skiplists in
+    /// order, same as [`OrderedSkipList::merge_iter`]
+    pub fn merge<'a>(&'a self, other: &'a OrderedSkipList<V>) -> MergeIter<'a, V> {
+        self.merge_iter(other)
+    }
+
+    /// Returns a lazy iterator producing the multiset union of both ordered
+    /// skiplists in order, an element occurring m times in `self` and n times
+    /// in `other` is produced max(m, n) times. Equal elements come from `self`
+    /// while it has them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut lhs = OrderedSkipList::new_duplicatable();
+    /// let mut rhs = OrderedSkipList::new_duplicatable();
+    /// for i in [1, 1, 2, 3].iter() {
+    ///     lhs.insert(*i);
+    /// }
+    /// for i in [1, 3, 3, 4].iter() {
+    ///     rhs.insert(*i);
+    /// }
+    ///
+    /// let union: Vec<_> = lhs.union(&rhs).cloned().collect();
+    /// assert_eq!(union, vec![1, 1, 2, 3, 3, 4]);
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a OrderedSkipList<V>) -> Union<'a, V> {
+        let mut lhs_iter = self.iter();
+        let mut rhs_iter = other.iter();
+        Union {
+            lhs_value: lhs_iter.next(),
+            rhs_value: rhs_iter.next(),
+            lhs_iter,
+            rhs_iter,
+        }
+    }
+
+    /// Returns a lazy iterator producing the multiset intersection of both
+    /// ordered skiplists in order, an element occurring m times in `self` and n
+    /// times in `other` is produced min(m, n) times. Elements come from `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut lhs = OrderedSkipList::new_duplicatable();
+    /// let mut rhs = OrderedSkipList::new_duplicatable();
+    /// for i in [1, 1, 2, 3, 3].iter() {
+    ///     lhs.insert(*i);
+    /// }
+    /// for i in [1, 3, 3, 4].iter() {
+    ///     rhs.insert(*i);
+    /// }
+    ///
+    /// let intersection: Vec<_> = lhs.intersection(&rhs).cloned().collect();
+    /// assert_eq!(intersection, vec![1, 3, 3]);
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a OrderedSkipList<V>) -> Intersection<'a, V> {
+        let mut lhs_iter = self.iter();
+        let mut rhs_iter = other.iter();
+        Intersection {
+            lhs_value: lhs_iter.next(),
+            rhs_value: rhs_iter.next(),
+            lhs_iter,
+            rhs_iter,
+        }
+    }
+
+    fn _value_range_to_index_range<R, Q: ?Sized>(&self, range: R) -> (usize, usize)
+    where
+        R: ValueRange<Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let left = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(q) => self._index_not_less(q),
+            Bound::Excluded(q) => self._index_not_less_or_equal(q),
+        };
+
+        let right = match range.end_bound() {
+            Bound::Unbounded => self.len(),
+            Bound::Included(q) => self._index_not_less_or_equal(q),
+            Bound::Excluded(q) => self._index_not_less(q),
+        };
+
+        (left, right)
+    }
+
+    fn _index_not_less<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk._seek_by(|v| Borrow::<Q>::borrow(v).cmp(q), false).1
+    }
+
+    fn _index_not_less_or_equal<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk._seek_by(|v| Borrow::<Q>::borrow(v).cmp(q), true).1
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(0);
+    /// sk.insert(1);
+    /// assert_eq!(sk.get(1), Some(&1));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Get the last element equals to q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// sk.insert(1);
+    /// sk.insert(0);
+    /// sk.insert(1);
+    /// sk.insert(2);
+    ///
+    /// assert_eq!(sk.get_last(&2), Some((3, &2)));
+    /// assert_eq!(sk.get_last(&1), Some((2, &1)));
+    /// assert_eq!(sk.get_last(&3), None);
+    /// ```
+    pub fn get_last<Q: ?Sized>(&self, q: &Q) -> Option<(usize, &V)>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk._get_last_by(|v| Borrow::<Q>::borrow(v).cmp(q))
+    }
+
+    /// Get the first element equals to q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
     /// sk.insert(1);
     /// sk.insert(0);
     /// sk.insert(1);
@@ -419,99 +787,1074 @@ impl<V: Ord> OrderedSkipList<V> {
         V: Borrow<Q>,
         Q: Ord,
     {
-        if self.len() == 0 {
+        self.sk._get_first_by(|v| Borrow::<Q>::borrow(v).cmp(q))
+    }
+
+    /// Get the first element that `f` reports equal, with its index. `f` compares
+    /// a stored element against the target, it should return `Less` for elements
+    /// before the target and be consistent with the order of the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert((1, "a"));
+    /// sk.insert((2, "b"));
+    /// sk.insert((2, "c"));
+    ///
+    /// assert_eq!(sk.find_by(|v| v.0.cmp(&2)), Some((1, &(2, "b"))));
+    /// assert_eq!(sk.find_by(|v| v.0.cmp(&3)), None);
+    /// ```
+    pub fn find_by<F>(&self, f: F) -> Option<(usize, &V)>
+    where
+        F: FnMut(&V) -> Ordering,
+    {
+        self.sk._get_first_by(f)
+    }
+
+    /// Returns the number of elements greater than q, which is the place of q
+    /// counted from the largest element starting at 0. Equal elements share the
+    /// same place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut scores = OrderedSkipList::new_duplicatable();
+    /// for score in [70, 90, 80, 90, 60].iter() {
+    ///     scores.insert(*score);
+    /// }
+    ///
+    /// assert_eq!(scores.rank_desc(&90), 0);
+    /// assert_eq!(scores.rank_desc(&80), 2);
+    /// assert_eq!(scores.rank_desc(&85), 2);
+    /// assert_eq!(scores.rank_desc(&0), 5);
+    /// ```
+    pub fn rank_desc<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.len() - self._index_not_less_or_equal(q)
+    }
+
+    /// Returns the element at the place `k` counted from the largest element
+    /// starting at 0, or `None` if `k` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..5 {
+    ///     sk.insert(i);
+    /// }
+    /// assert_eq!(sk.select_desc(0), Some(&4));
+    /// assert_eq!(sk.select_desc(4), Some(&0));
+    /// assert_eq!(sk.select_desc(5), None);
+    /// ```
+    pub fn select_desc(&self, k: usize) -> Option<&V> {
+        if k >= self.len() {
             return None;
         }
+        self.get(self.len() - 1 - k)
+    }
 
-        let sk = &self.sk;
-        let mut cur_level = sk.head.links.len() - 1;
-        let mut cur_index = 0;
-        let mut cur_ptr: *const _ = &*sk.head;
-        let mut has_equal = false;
+    /// Get the first element equal to every query.
+    ///
+    /// The skiplist is walked once for ascending queries, every search resumes
+    /// from where the previous one stopped. Queries in other orders are still
+    /// answered correctly, but not faster than searching them one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..10 {
+    ///     sk.insert(i * 2);
+    /// }
+    ///
+    /// assert_eq!(sk.get_batch(&[2, 3, 8]), vec![Some(&2), None, Some(&8)]);
+    /// assert_eq!(sk.contains_batch(&[2, 3, 8]), vec![true, false, true]);
+    /// ```
+    pub fn get_batch<'q, Q: 'q + ?Sized, I>(&self, sorted_queries: I) -> Vec<Option<&V>>
+    where
+        I: IntoIterator<Item = &'q Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut seek_path = SeekPath::new(&self.sk);
+        sorted_queries
+            .into_iter()
+            .map(|q| {
+                seek_path
+                    .get_first_by(|v| Borrow::<Q>::borrow(v).cmp(q))
+                    .map(|(_, v)| v)
+            })
+            .collect()
+    }
+
+    /// Check if there are elements equal to the queries, see
+    /// [`get_batch`](#method.get_batch)
+    pub fn contains_batch<'q, Q: 'q + ?Sized, I>(&self, sorted_queries: I) -> Vec<bool>
+    where
+        I: IntoIterator<Item = &'q Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_batch(sorted_queries)
+            .into_iter()
+            .map(|found| found.is_some())
+            .collect()
+    }
+
+    /// Search by a key the values are ordered by, without constructing a value
+    /// to compare with. Returns `Ok` with the index of the first matching value,
+    /// or `Err` with the index where a value of the key would be inserted.
+    ///
+    /// The order of the keys must agree with the order of the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert((2, "b"));
+    /// sk.insert((1, "a"));
+    ///
+    /// assert_eq!(sk.binary_search_by_key(&2, |&(id, _)| id), Ok(1));
+    /// assert_eq!(sk.binary_search_by_key(&5, |&(id, _)| id), Err(2));
+    /// ```
+    pub fn binary_search_by_key<B, F>(&self, b: &B, f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&V) -> B,
+        B: Ord,
+    {
+        self.sk.binary_search_by_key(b, f)
+    }
+
+    /// Insert value, if the ordered skiplist if duplicatable return None after inserted
+    /// if it's not duplicatable and the value is duplicated return the old one
+    ///
+    /// In a duplicatable ordered skiplist, the value is put before the values equal
+    /// to it, same as [`insert_before_equal`](#method.insert_before_equal).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(0);
+    /// sk.insert(1);
+    /// sk.insert(0);
+    /// assert_eq!(sk.get(1), Some(&1));
+    /// ```
+    pub fn insert(&mut self, value: V) -> Option<V> {
+        self.sk._insert_by(value, self.duplicatable, |a, b| a.cmp(b)).1
+    }
+
+    /// Insert a value the caller guarantees isn't in the ordered skiplist, the
+    /// check for an equal value is skipped. It's only verified in debug builds,
+    /// a non-duplicatable ordered skiplist is broken if the value exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in (0..10).rev() {
+    ///     sk.insert_unique_unchecked(i);
+    /// }
+    /// assert_eq!(sk.get(3), Some(&3));
+    /// ```
+    pub fn insert_unique_unchecked(&mut self, value: V) {
+        debug_assert!(
+            self.get_first(&value).is_none(),
+            "value should not be in the ordered skiplist"
+        );
+        self.sk._insert_by(value, true, |a, b| a.cmp(b));
+    }
+
+    /// Insert values of the iterator, values that are in order after the last
+    /// element are appended at the tail in splices without being searched,
+    /// others are inserted one by one. Returns number of appended values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(0);
+    /// assert_eq!(sk.extend_from_sorted(vec![1, 2, 5, 3, 6]), 4);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3, 5, 6]);
+    /// ```
+    pub fn extend_from_sorted<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = V>,
+    {
+        let mut pending = vec![];
+        let mut appended = 0;
+        for value in iter {
+            let in_order = match pending.last().or_else(|| self.back()) {
+                None => true,
+                Some(last) if self.duplicatable => *last <= value,
+                Some(last) => *last < value,
+            };
+            if in_order {
+                pending.push(value);
+            } else {
+                appended += self.sk._insert_iter_at(self.len(), pending.drain(..));
+                self.insert(value);
+            }
+        }
+        appended + self.sk._insert_iter_at(self.len(), pending)
+    }
+
+    /// Insert value before the values equal to it, so among equal values the
+    /// latest inserted one comes first (LIFO).
+    ///
+    /// If it's not duplicatable and the value is duplicated, the old one is
+    /// replaced and returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// sk.insert_before_equal(1);
+    /// sk.insert_before_equal(0);
+    /// sk.insert_before_equal(1);
+    /// assert_eq!(sk.get_last(&1), Some((2, &1)));
+    /// ```
+    pub fn insert_before_equal(&mut self, value: V) -> Option<V> {
+        self.insert(value)
+    }
+
+    /// Insert value after the values equal to it, so among equal values the
+    /// earliest inserted one comes first (FIFO).
+    ///
+    /// If it's not duplicatable and the value is duplicated, the old one is
+    /// replaced and returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    /// use std::cmp::Ordering;
+    ///
+    /// // jobs with the same priority
+    /// #[derive(Debug, PartialEq, Eq)]
+    /// struct Job(u32, &'static str);
+    ///
+    /// impl Ord for Job {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         self.0.cmp(&other.0)
+    ///     }
+    /// }
+    ///
+    /// impl PartialOrd for Job {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         Some(self.cmp(other))
+    ///     }
+    /// }
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// sk.insert_after_equal(Job(1, "a"));
+    /// sk.insert_after_equal(Job(1, "b"));
+    /// sk.insert_after_equal(Job(0, "c"));
+    /// assert_eq!(sk.pop_front(), Some(Job(0, "c")));
+    /// assert_eq!(sk.pop_front(), Some(Job(1, "a")));
+    /// ```
+    pub fn insert_after_equal(&mut self, value: V) -> Option<V> {
+        if !self.duplicatable {
+            return self.insert(value);
+        }
+        // Treat equal values as less, so the value is put after them.
+        self.sk
+            ._insert_by(value, true, |a, b| match a.cmp(b) {
+                Ordering::Equal => Ordering::Less,
+                ord => ord,
+            })
+            .1
+    }
+
+    /// Remove item at the index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    ///
+    pub fn remove(&mut self, index: usize) -> V {
+        self.sk.remove(index)
+    }
+
+    /// Remove the first item equals to q, returns the removed value
+    pub fn remove_first<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let first = self.get_first(q);
+        match first {
+            None => None,
+            Some((index, _)) => Some(self.remove(index)),
+        }
+    }
+
+    /// Remove the last item equals to q, returns the removed value
+    pub fn remove_last<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let last = self.get_last(q);
+        match last {
+            None => None,
+            Some((index, _)) => Some(self.remove(index)),
+        }
+    }
+
+    /// Remove the all items equals to q, returns number of items removed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// sk.insert(0);
+    /// sk.insert(0);
+    /// sk.insert(0);
+    ///
+    /// sk.remove_value(&0);
+    /// assert_eq!(sk.len(), 0);
+    /// ```
+    pub fn remove_value<Q: ?Sized>(&mut self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let left = match self.get_first(q) {
+            None => return 0,
+            Some((index, _)) => index,
+        };
+
+        let right = match self.get_last(q) {
+            None => unreachable!(),
+            Some((index, _)) => index + 1,
+        };
+
+        self.sk.remove_range(left..right)
+    }
+
+    /// Returns the first value in the skiplist
+    /// same as [`SkipList::front`]: trait.SkipList.html#method.front
+    pub fn front(&self) -> Option<&V> {
+        self.sk.front()
+    }
+
+    /// Returns the last value in the skiplist
+    /// same as [`SkipList::back`]: trait.SkipList.html#method.back
+    pub fn back(&self) -> Option<&V> {
+        self.sk.back()
+    }
+
+    /// Pop the first value in the skiplist
+    /// same as [`SkipList::pop_front`]: trait.SkipList.html#method.pop_front
+    pub fn pop_front(&mut self) -> Option<V> {
+        self.sk.pop_front()
+    }
+
+    /// Pop the last value in the skiplist
+    /// same as [`SkipList::pop_back`]: trait.SkipList.html#method.pop_back
+    pub fn pop_back(&mut self) -> Option<V> {
+        self.sk.pop_back()
+    }
+
+    /// Returns the smallest value, same as [`OrderedSkipList::front`]
+    pub fn first(&self) -> Option<&V> {
+        self.front()
+    }
+
+    /// Returns the largest value, same as [`OrderedSkipList::back`]
+    pub fn last(&self) -> Option<&V> {
+        self.back()
+    }
+
+    /// Pop the smallest value, same as [`OrderedSkipList::pop_front`]
+    pub fn pop_first(&mut self) -> Option<V> {
+        self.pop_front()
+    }
+
+    /// Pop the largest value, same as [`OrderedSkipList::pop_back`]
+    pub fn pop_last(&mut self) -> Option<V> {
+        self.pop_back()
+    }
+
+    /// Returns the index of the first value equal to q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// for i in [1, 2, 2, 3].iter() {
+    ///     sk.insert(*i);
+    /// }
+    /// assert_eq!(sk.first_index_of(&2), Some(1));
+    /// assert_eq!(sk.last_index_of(&2), Some(2));
+    /// assert_eq!(sk.first_index_of(&4), None);
+    /// assert_eq!(sk.pop_first(), Some(1));
+    /// assert_eq!(sk.last(), Some(&3));
+    /// ```
+    pub fn first_index_of<Q: ?Sized>(&self, q: &Q) -> Option<usize>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_first(q).map(|(index, _)| index)
+    }
+
+    /// Returns the index of the last value equal to q
+    pub fn last_index_of<Q: ?Sized>(&self, q: &Q) -> Option<usize>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_last(q).map(|(index, _)| index)
+    }
+
+    /// Returns graph that contains a range of elements of the skiplist
+    /// same as [`SkipList::explain`]: trait.SkipList.html#method.explain
+    pub fn explain<R>(&self, range: R) -> Result<String, &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+    {
+        self.sk.explain(range)
+    }
+
+    /// Write the graph of `explain` to the writer, same as
+    /// [`SkipList::explain_to`](../skiplist/struct.SkipList.html#method.explain_to)
+    pub fn explain_to<R, W>(&self, range: R, w: &mut W) -> Result<(), &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+        W: std::fmt::Write,
+    {
+        self.sk.explain_to(range, w)
+    }
+
+    /// Returns graph that contains elements in the value range, like
+    /// [`explain`](#method.explain) but the window is located by values
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// for i in 0..100 {
+    ///     sk.insert(i * 2);
+    /// }
+    ///
+    /// let graph = sk.explain_by_value(&41..&50).unwrap();
+    /// assert!(graph.starts_with("start: 21, levels: "));
+    /// assert!(graph.contains("show_len: 4,"));
+    /// ```
+    pub fn explain_by_value<R, Q: ?Sized>(&self, range: R) -> Result<String, &'static str>
+    where
+        V: std::fmt::Display + Borrow<Q>,
+        R: ValueRange<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self._value_range_to_index_range(range);
+        self.sk.explain(left..right)
+    }
+}
+
+impl<V: Ord + KeyedValue> OrderedSkipList<V> {
+    /// Returns an iterator of keys and mutable payloads of the elements
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use skiplist::ordered_skiplist::{KeyedValue, OrderedSkipList};
+    ///
+    /// struct Player {
+    ///     score: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// impl KeyedValue for Player {
+    ///     type Key = u32;
+    ///     type Payload = String;
+    ///
+    ///     fn split_mut(&mut self) -> (&u32, &mut String) {
+    ///         (&self.score, &mut self.name)
+    ///     }
+    /// }
+    ///
+    /// impl Ord for Player {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         self.score.cmp(&other.score)
+    ///     }
+    /// }
+    /// impl PartialOrd for Player {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         Some(self.cmp(other))
+    ///     }
+    /// }
+    /// impl PartialEq for Player {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.score == other.score
+    ///     }
+    /// }
+    /// impl Eq for Player {}
+    ///
+    /// let mut sk = OrderedSkipList::new();
+    /// sk.insert(Player { score: 20, name: "b".to_string() });
+    /// sk.insert(Player { score: 10, name: "a".to_string() });
+    /// for (score, name) in sk.iter_mut() {
+    ///     name.push_str(&score.to_string());
+    /// }
+    /// assert_eq!(sk.get(0).unwrap().name, "a10");
+    /// ```
+    pub fn iter_mut(&mut self) -> PayloadIterMut<'_, V> {
+        PayloadIterMut {
+            inner: self.sk.iter_mut(),
+        }
+    }
+
+    /// Returns an iterator of keys and mutable payloads of the elements in the
+    /// range
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    pub fn range_mut<R, Q: ?Sized>(&mut self, range: R) -> PayloadRangeMut<'_, V>
+    where
+        R: ValueRange<Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = if self.len() == 0 {
+            (0, 0)
+        } else {
+            self._value_range_to_index_range(range)
+        };
+        PayloadRangeMut {
+            inner: self.sk.range_mut(left..right),
+        }
+    }
+}
+
+impl<V: Ord> Extend<V> for OrderedSkipList<V> {
+    /// Insert values of the iterator one by one
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a, V: Ord + Copy + 'a> Extend<&'a V> for OrderedSkipList<V> {
+    /// Insert copies of values of the iterator one by one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk: OrderedSkipList<i32> = OrderedSkipList::new();
+    /// sk.extend([3, 1, 2].iter());
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    fn extend<I: IntoIterator<Item = &'a V>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<V: Ord> From<BinaryHeap<V>> for OrderedSkipList<V> {
+    /// Create a duplicatable ordered skiplist from the values of the heap, they
+    /// are sorted by the heap and linked in one splice
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BinaryHeap;
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let heap: BinaryHeap<_> = vec![3, 1, 3, 2].into_iter().collect();
+    /// let sk = OrderedSkipList::from(heap);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3, 3]);
+    /// assert_eq!(sk.get(1), Some(&2));
+    /// ```
+    fn from(heap: BinaryHeap<V>) -> Self {
+        let mut sk = Self::new_duplicatable();
+        sk.sk.extend_front(heap.into_sorted_vec());
+        sk
+    }
+}
+
+impl<V: Ord> IntoIterator for OrderedSkipList<V> {
+    type Item = V;
+    type IntoIter = IntoIter<V>;
+
+    /// Returns a moved iterator of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    /// let mut idx = 0;
+    /// for value in sk.into_iter() {
+    ///     assert_eq!(value, idx);
+    ///     idx += 1;
+    /// }
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.sk.into_iter()
+    }
+}
+
+/// A lazy iterator producing elements of two ordered skiplists in order.
+///
+/// This `struct` is created by the [`merge_iter`] method on
+/// [`OrderedSkipList`]. See its documentation for more.
+///
+/// [`OrderedSkipList`]: struct.OrderedSkipList.html
+/// [`merge_iter`]: struct.OrderedSkipList.html#method.merge_iter
+#[derive(Debug)]
+pub struct MergeIter<'a, V: Ord> {
+    lhs_iter: Iter<'a, V>,
+    rhs_iter: Iter<'a, V>,
+    lhs_value: Option<&'a V>,
+    rhs_value: Option<&'a V>,
+}
+
+impl<'a, V: Ord> Clone for MergeIter<'a, V> {
+    fn clone(&self) -> Self {
+        MergeIter {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
+impl<'a, V: Ord> Iterator for MergeIter<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let take_lhs = match (self.lhs_value, self.rhs_value) {
+            (None, None) => return None,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(lhs), Some(rhs)) => lhs <= rhs,
+        };
+
+        if take_lhs {
+            let result = self.lhs_value.take();
+            self.lhs_value = self.lhs_iter.next();
+            result
+        } else {
+            let result = self.rhs_value.take();
+            self.rhs_value = self.rhs_iter.next();
+            result
+        }
+    }
+}
+
+impl<'a, V: Ord> FusedIterator for MergeIter<'a, V> {}
+
+/// A lazy iterator producing the multiset union of two ordered skiplists in
+/// order.
+///
+/// This `struct` is created by the [`union`] method on [`OrderedSkipList`].
+/// See its documentation for more.
+///
+/// [`OrderedSkipList`]: struct.OrderedSkipList.html
+/// [`union`]: struct.OrderedSkipList.html#method.union
+#[derive(Debug)]
+pub struct Union<'a, V: Ord> {
+    lhs_iter: Iter<'a, V>,
+    rhs_iter: Iter<'a, V>,
+    lhs_value: Option<&'a V>,
+    rhs_value: Option<&'a V>,
+}
+
+impl<'a, V: Ord> Clone for Union<'a, V> {
+    fn clone(&self) -> Self {
+        Union {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
+impl<'a, V: Ord> Iterator for Union<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ord = match (self.lhs_value, self.rhs_value) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(lhs), Some(rhs)) => lhs.cmp(rhs),
+        };
+
+        match ord {
+            Ordering::Less => {
+                let result = self.lhs_value.take();
+                self.lhs_value = self.lhs_iter.next();
+                result
+            }
+            Ordering::Greater => {
+                let result = self.rhs_value.take();
+                self.rhs_value = self.rhs_iter.next();
+                result
+            }
+            Ordering::Equal => {
+                // pair one equal element of each side
+                let result = self.lhs_value.take();
+                self.lhs_value = self.lhs_iter.next();
+                self.rhs_value = self.rhs_iter.next();
+                result
+            }
+        }
+    }
+}
+
+impl<'a, V: Ord> FusedIterator for Union<'a, V> {}
+
+/// A lazy iterator producing the multiset intersection of two ordered
+/// skiplists in order.
+///
+/// This `struct` is created by the [`intersection`] method on
+/// [`OrderedSkipList`]. See its documentation for more.
+///
+/// [`OrderedSkipList`]: struct.OrderedSkipList.html
+/// [`intersection`]: struct.OrderedSkipList.html#method.intersection
+#[derive(Debug)]
+pub struct Intersection<'a, V: Ord> {
+    lhs_iter: Iter<'a, V>,
+    rhs_iter: Iter<'a, V>,
+    lhs_value: Option<&'a V>,
+    rhs_value: Option<&'a V>,
+}
+
+impl<'a, V: Ord> Clone for Intersection<'a, V> {
+    fn clone(&self) -> Self {
+        Intersection {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
+impl<'a, V: Ord> Iterator for Intersection<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let (Some(lhs), Some(rhs)) = (self.lhs_value, self.rhs_value) {
+            match lhs.cmp(rhs) {
+                Ordering::Less => self.lhs_value = self.lhs_iter.next(),
+                Ordering::Greater => self.rhs_value = self.rhs_iter.next(),
+                Ordering::Equal => {
+                    self.lhs_value = self.lhs_iter.next();
+                    self.rhs_value = self.rhs_iter.next();
+                    return Some(lhs);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, V: Ord> FusedIterator for Intersection<'a, V> {}
+
+pub struct PayloadIterMut<'a, V> {
+    inner: IterMut<'a, V>,
+}
+
+impl<'a, V: KeyedValue> Iterator for PayloadIterMut<'a, V> {
+    type Item = (&'a V::Key, &'a mut V::Payload);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|value| value.split_mut())
+    }
+}
+
+impl<'a, V: KeyedValue> FusedIterator for PayloadIterMut<'a, V> {}
+
+pub struct PayloadRangeMut<'a, V> {
+    inner: RangeMut<'a, V>,
+}
+
+impl<'a, V: KeyedValue> Iterator for PayloadRangeMut<'a, V> {
+    type Item = (&'a V::Key, &'a mut V::Payload);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|value| value.split_mut())
+    }
+}
+
+impl<'a, V: KeyedValue> FusedIterator for PayloadRangeMut<'a, V> {}
+
+/// Nodes where the last search stopped in every level, so a search can resume
+/// from them instead of the head.
+///
+/// Searching ascending targets walks the skiplist once. Nodes that are not less
+/// than the current target are not reused, so targets in other orders are still
+/// found correctly, only slower.
+pub(crate) struct SeekPath<'a, V> {
+    sk: &'a SkipList<V>,
+    // node and its index in every level
+    path: Vec<(*const Node<V>, usize)>,
+}
+
+impl<'a, V> SeekPath<'a, V> {
+    pub(crate) fn new(sk: &'a SkipList<V>) -> Self {
+        let head: *const Node<V> = &*sk.head;
+        SeekPath {
+            sk,
+            path: vec![(head, 0); sk.head.links.len()],
+        }
+    }
+
+    /// Returns the first element that `f` reports equal, with its index
+    pub(crate) fn get_first_by<F>(&mut self, mut f: F) -> Option<(usize, &'a V)>
+    where
+        F: FnMut(&V) -> Ordering,
+    {
+        self.sk.metrics.search();
+        let mut cur_ptr: *const Node<V> = &*self.sk.head;
+        let mut cur_index = 0;
+        for level in (0..self.path.len()).rev() {
+            let (path_ptr, path_index) = self.path[level];
+            // Safety: nodes in path will never be null and always valid.
+            let path_node = unsafe { &*path_ptr };
+            if path_index > cur_index
+                && matches!(path_node.value.as_ref(), Some(v) if f(v) == Ordering::Less)
+            {
+                cur_ptr = path_ptr;
+                cur_index = path_index;
+            }
+            loop {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() {
+                    break;
+                }
+                // Safety: next_ptr is checked that it won't be null
+                let next_value = unsafe { &*next_ptr }
+                    .value
+                    .as_ref()
+                    .expect("there must be value in a normal node");
+                if f(next_value) != Ordering::Less {
+                    break;
+                }
+                cur_index += cur.links_len[level];
+                cur_ptr = next_ptr;
+                self.sk.metrics.visit(level);
+            }
+            self.path[level] = (cur_ptr, cur_index);
+        }
+
+        // Safety: cur_ptr will never be null and always valid, and the node
+        // lives as long as the borrowed skiplist.
+        let next = unsafe { &*cur_ptr }.next.as_ref()?;
+        let value = next.value.as_ref().expect("normal node always has a value");
+        match f(value) {
+            Ordering::Equal => Some((cur_index, value)),
+            _ => None,
+        }
+    }
+}
+
+// Searching helpers shared by the ordered containers. Every closure compares a
+// stored element against the target, so `Ordering::Less` means the element is
+// located before the target.
+impl<V> SkipList<V> {
+    /// Walk from head and stop before the first element that is not less than
+    /// the target (or not less or equal to it if `or_equal` is true).
+    ///
+    /// Returns the pointer of the node where the walk stopped, which may be head,
+    /// and the index of the element right after it.
+    pub(crate) fn _seek_by<F>(&self, mut f: F, or_equal: bool) -> (*const Node<V>, usize)
+    where
+        F: FnMut(&V) -> Ordering,
+    {
+        self.metrics.search();
+        let mut cur_ptr: *const Node<V> = &*self.head;
+        let mut cur_index = 0;
+        let mut cur_level = self.head.links.len();
 
-        loop {
+        while cur_level > 0 {
             // Safety: cur_ptr will never be null and always valid.
-            let cur_node = unsafe { &*cur_ptr };
-            if cur_node.links[cur_level].is_null() {
-                if cur_level == 0 {
-                    break;
-                }
-                cur_level -= 1;
-                continue;
-            }
-
-            // Safety: next_ptr will not be null when the program run to here
-            let next_value = unsafe {
-                (*cur_node.links[cur_level])
+            let cur = unsafe { &*cur_ptr };
+            let next_ptr = cur.links[cur_level - 1];
+            if !next_ptr.is_null() {
+                // Safety: next_ptr is checked that it won't be null
+                let next_value = unsafe { &*next_ptr }
                     .value
                     .as_ref()
-                    .expect("there must be value in a normal node")
-            };
-            match next_value.borrow().cmp(q) {
-                Ordering::Less => {
-                    cur_ptr = cur_node.links[cur_level];
-                    cur_index += cur_node.links_len[cur_level];
+                    .expect("there must be value in a normal node");
+                let forward = match f(next_value) {
+                    Ordering::Less => true,
+                    Ordering::Equal => or_equal,
+                    Ordering::Greater => false,
+                };
+                if forward {
+                    cur_index += cur.links_len[cur_level - 1];
+                    cur_ptr = next_ptr;
+                    self.metrics.visit(cur_level - 1);
                     continue;
                 }
-                Ordering::Equal => {
-                    has_equal = true;
-                }
-                Ordering::Greater => (),
-            }
-
-            if cur_level == 0 {
-                break;
             }
             cur_level -= 1;
         }
 
-        if !has_equal {
-            return None;
-        }
+        (cur_ptr, cur_index)
+    }
 
-        // Safety: cur_ptr will never be null and always valid.
-        let v = unsafe {
-            (*cur_ptr)
-                .next
-                .as_ref()
-                .and_then(|next| next.value.as_ref())
+    /// Returns whether any element is in the bounds with one search for the
+    /// start bound, `cmp` compares an element against a bound
+    pub(crate) fn _any_in_range_by<Q: ?Sized, F>(
+        &self,
+        start: Bound<&Q>,
+        end: Bound<&Q>,
+        mut cmp: F,
+    ) -> bool
+    where
+        F: FnMut(&V, &Q) -> Ordering,
+    {
+        let prev_ptr = match start {
+            Bound::Unbounded => &*self.head as *const Node<V>,
+            Bound::Included(q) => self._seek_by(|v| cmp(v, q), false).0,
+            Bound::Excluded(q) => self._seek_by(|v| cmp(v, q), true).0,
         };
+        // Safety: prev_ptr will never be null and always valid.
+        let next = match unsafe { &*prev_ptr }.next.as_deref() {
+            None => return false,
+            Some(next) => next,
+        };
+        let value = next.value.as_ref().expect("normal node always has a value");
+        match end {
+            Bound::Unbounded => true,
+            Bound::Included(q) => cmp(value, q) != Ordering::Greater,
+            Bound::Excluded(q) => cmp(value, q) == Ordering::Less,
+        }
+    }
+
+    /// Returns the first element that `f` reports equal, with its index
+    pub(crate) fn _get_first_by<F>(&self, mut f: F) -> Option<(usize, &V)>
+    where
+        F: FnMut(&V) -> Ordering,
+    {
+        let (prev_ptr, index) = self._seek_by(&mut f, false);
+        // Safety: prev_ptr will never be null and always valid.
+        let next = unsafe { &*prev_ptr }.next.as_ref()?;
+        let value = next.value.as_ref().expect("normal node always has a value");
+        match f(value) {
+            Ordering::Equal => Some((index, value)),
+            _ => None,
+        }
+    }
 
-        // cur_index is prev index added by 1
-        // so the node index which is prev index plus one equals to cur_index
-        v.map(|v| (cur_index, v))
+    /// Returns the last element that `f` reports equal, with its index
+    pub(crate) fn _get_last_by<F>(&self, mut f: F) -> Option<(usize, &V)>
+    where
+        F: FnMut(&V) -> Ordering,
+    {
+        let (prev_ptr, index) = self._seek_by(&mut f, true);
+        // Safety: prev_ptr will never be null and always valid.
+        // head has no value, so nothing is found if the walk stopped at head.
+        let value = unsafe { &*prev_ptr }.value.as_ref()?;
+        match f(value) {
+            Ordering::Equal => Some((index - 1, value)),
+            _ => None,
+        }
     }
 
-    /// Insert value, if the ordered skiplist if duplicatable return None after inserted
-    /// if it's not duplicatable and the value is duplicated return the old one
+    /// Insert value at the position decided by `cmp`, new value is put before
+    /// the elements equal to it.
     ///
-    /// # Example
+    /// If `dup` is false and there is an element equal to value, the element is
+    /// replaced and returned.
     ///
-    /// ```
-    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    /// Returns pointer of the node which holds the value as well.
+    pub(crate) fn _insert_by<F>(&mut self, value: V, dup: bool, cmp: F) -> (*mut Node<V>, Option<V>)
+    where
+        F: FnMut(&V, &V) -> Ordering,
+    {
+        let level = self.level_generator.choose();
+        let node = self._new_node(value, level + 1);
+        self._insert_node_by(node, dup, cmp)
+    }
+
+    /// Same as `_insert_by`, but links a node which holds the value, the node may
+    /// be one unlinked from a skiplist, so its level is reused.
     ///
-    /// let mut sk = OrderedSkipList::new();
-    /// sk.insert(0);
-    /// sk.insert(1);
-    /// sk.insert(0);
-    /// assert_eq!(sk.get(1), Some(&1));
-    /// ```
-    pub fn insert(&mut self, value: V) -> Option<V> {
-        // create a node
-        let sk = &mut self.sk;
-        let level = sk.level_generator.choose();
-        let mut node = Box::new(Node::new(None, level + 1));
+    /// If the node isn't linked, it's dropped after its value is moved out.
+    pub(crate) fn _insert_node_by<F>(
+        &mut self,
+        mut node: Box<Node<V>>,
+        dup: bool,
+        mut cmp: F,
+    ) -> (*mut Node<V>, Option<V>)
+    where
+        F: FnMut(&V, &V) -> Ordering,
+    {
+        self._forget_finger();
+        self._bump_version();
+        let level = node.links.len() - 1;
+        for i in 0..=level {
+            node.links[i] = std::ptr::null_mut();
+            node.links_len[i] = 0;
+        }
+        node.next = None;
+        node.prev = std::ptr::null_mut();
         let node_ptr: *mut _ = &mut *node;
 
-        while level >= sk.head.links.len() {
-            sk.head.increase_level();
+        while level >= self.head.links.len() {
+            self.head.increase_level();
         }
 
         // get previous nodes for later use
-        let total_level = sk.head.links.len();
+        let total_level = self.head.links.len();
         let mut prev_ptrs = vec![std::ptr::null_mut(); total_level];
         let mut prev_indexs = vec![0; total_level];
-        let mut cur_ptr: *mut _ = &mut *sk.head;
+        let mut cur_ptr: *mut _ = &mut *self.head;
         let mut cur_index = 0;
         let mut cur_level = total_level - 1;
         let mut has_equal = false;
@@ -520,8 +1863,9 @@ impl<V: Ord> OrderedSkipList<V> {
             prev_indexs[cur_level] = cur_index;
 
             // Safety: cur_ptr will never be null and always valid.
-            let next_ptr = unsafe { (*cur_ptr).links[cur_level] };
-            let cur_len = unsafe { (*cur_ptr).links_len[cur_level] };
+            let cur = unsafe { &*cur_ptr };
+            let next_ptr = cur.links[cur_level];
+            let cur_len = cur.links_len[cur_level];
             if next_ptr.is_null() {
                 if cur_level == 0 {
                     break;
@@ -531,15 +1875,15 @@ impl<V: Ord> OrderedSkipList<V> {
             }
 
             // Safety: next_ptr will not be null when the program run to here.
-            let next_value = unsafe {
-                (*next_ptr)
-                    .value
-                    .as_ref()
-                    .expect("there must be value in a normal node")
-            };
-            match next_value.cmp(&value) {
+            let next_value = unsafe { &*next_ptr }
+                .value
+                .as_ref()
+                .expect("there must be value in a normal node");
+            let value = node.value.as_ref().expect("node to insert must have a value");
+            match cmp(next_value, value) {
                 Ordering::Less => {
                     cur_ptr = next_ptr;
+                    self.metrics.visit(cur_level);
                     cur_index += cur_len;
                     continue;
                 }
@@ -556,17 +1900,16 @@ impl<V: Ord> OrderedSkipList<V> {
         }
 
         // if duplicated and not duplicatable, replace the old one
-        if has_equal && !self.duplicatable {
+        if has_equal && !dup {
             // Safety: cur_ptr will never be null and always valid.
-            return unsafe {
-                (*cur_ptr)
-                    .next
-                    .as_mut()
-                    .and_then(|node| node.replace(value))
-            };
+            let the_node = unsafe { &mut *cur_ptr }
+                .next
+                .as_mut()
+                .expect("there must be a node equal to value");
+            let old = the_node.replace(node.value.take().expect("node to insert must have a value"));
+            return (&mut **the_node, old);
         }
 
-        node.value = Some(value);
         let node_index = prev_indexs[0] + 1;
 
         // modify links
@@ -605,145 +1948,37 @@ impl<V: Ord> OrderedSkipList<V> {
         node.prev = cur_ptr;
         prev.next = Some(node);
 
-        self.sk.length += 1;
-
-        None
-    }
-
-    /// Remove item at the index
-    ///
-    /// # Panics
-    ///
-    /// Panics if index is out of bounds
-    ///
-    pub fn remove(&mut self, index: usize) -> V {
-        self.sk.remove(index)
-    }
-
-    /// Remove the first item equals to q, returns the removed value
-    pub fn remove_first<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
-    where
-        V: Borrow<Q>,
-        Q: Ord,
-    {
-        let first = self.get_first(q);
-        match first {
-            None => None,
-            Some((index, _)) => Some(self.remove(index)),
-        }
-    }
-
-    /// Remove the last item equals to q, returns the removed value
-    pub fn remove_last<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
-    where
-        V: Borrow<Q>,
-        Q: Ord,
-    {
-        let last = self.get_last(q);
-        match last {
-            None => None,
-            Some((index, _)) => Some(self.remove(index)),
-        }
-    }
-
-    /// Remove the all items equals to q, returns number of items removed
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use skiplist::ordered_skiplist::OrderedSkipList;
-    ///
-    /// let mut sk = OrderedSkipList::new_duplicatable();
-    /// sk.insert(0);
-    /// sk.insert(0);
-    /// sk.insert(0);
-    ///
-    /// sk.remove_value(&0);
-    /// assert_eq!(sk.len(), 0);
-    /// ```
-    pub fn remove_value<Q: ?Sized>(&mut self, q: &Q) -> usize
-    where
-        V: Borrow<Q>,
-        Q: Ord,
-    {
-        let left = match self.get_first(q) {
-            None => return 0,
-            Some((index, _)) => index,
-        };
-
-        let right = match self.get_last(q) {
-            None => unreachable!(),
-            Some((index, _)) => index + 1,
-        };
-
-        self.sk.remove_range(left..right)
-    }
-
-    /// Returns the first value in the skiplist
-    /// same as [`SkipList::front`]: trait.SkipList.html#method.front
-    pub fn front(&self) -> Option<&V> {
-        self.sk.front()
-    }
-
-    /// Returns the last value in the skiplist
-    /// same as [`SkipList::back`]: trait.SkipList.html#method.back
-    pub fn back(&self) -> Option<&V> {
-        self.sk.back()
-    }
-
-    /// Pop the first value in the skiplist
-    /// same as [`SkipList::pop_front`]: trait.SkipList.html#method.pop_front
-    pub fn pop_front(&mut self) -> Option<V> {
-        self.sk.pop_front()
-    }
-
-    /// Pop the last value in the skiplist
-    /// same as [`SkipList::pop_back`]: trait.SkipList.html#method.pop_back
-    pub fn pop_back(&mut self) -> Option<V> {
-        self.sk.pop_back()
-    }
-
-    /// Returns graph that contains a range of elements of the skiplist
-    /// same as [`SkipList::explain`]: trait.SkipList.html#method.explain
-    pub fn explain<R>(&self, range: R) -> Result<String, &'static str>
-    where
-        V: std::fmt::Display,
-        R: RangeBounds<usize>,
-    {
-        self.sk.explain(range)
-    }
-}
-
-impl<V: Ord> IntoIterator for OrderedSkipList<V> {
-    type Item = V;
-    type IntoIter = IntoIter<V>;
+        self.length += 1;
+        self.metrics.insert(1);
 
-    /// Returns a moved iterator of the skiplist
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use skiplist::skiplist::SkipList;
-    ///
-    /// let mut sk = SkipList::new();
-    /// for i in 0..10 {
-    ///     sk.push_back(i);
-    /// }
-    /// let mut idx = 0;
-    /// for value in sk.into_iter() {
-    ///     assert_eq!(value, idx);
-    ///     idx += 1;
-    /// }
-    /// ```
-    fn into_iter(self) -> Self::IntoIter {
-        self.sk.into_iter()
+        (node_ptr, None)
     }
 }
 
-
 #[cfg(test)]
 mod test {
     use super::*;
+    #[test]
+    fn binary_heap() {
+        let values: Vec<i32> = (0..500).map(|i| (i * 7919) % 101).collect();
+        let heap: BinaryHeap<_> = values.iter().cloned().collect();
+        let mut sk = OrderedSkipList::from(heap);
+        sk.sk._check_integrity();
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert!(sk.iter().eq(sorted.iter()));
+        sk.insert(50);
+        assert_eq!(sk.len(), 501);
+
+        let mut heap = sk.into_binary_heap();
+        sorted.push(50);
+        sorted.sort();
+        while let Some(max) = heap.pop() {
+            assert_eq!(sorted.pop(), Some(max));
+        }
+        assert!(sorted.is_empty());
+    }
+
     #[test]
     fn ordered_skiplist_no_dup_insert() {
         let mut sk = OrderedSkipList::new();
@@ -804,6 +2039,87 @@ mod test {
         assert_eq!(sk.get(4), Some(&2));
     }
 
+    // Compares by the first field only
+    #[derive(Debug)]
+    struct Job(u32, char);
+
+    impl PartialEq for Job {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Job {}
+
+    impl PartialOrd for Job {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Job {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn insert_among_equal() {
+        let mut sk = OrderedSkipList::new_duplicatable();
+        sk.insert_after_equal(Job(1, 'a'));
+        sk.insert_after_equal(Job(1, 'b'));
+        sk.insert_before_equal(Job(1, 'c'));
+        sk.insert(Job(1, 'd'));
+        sk.insert_after_equal(Job(0, 'e'));
+        sk.insert_after_equal(Job(1, 'f'));
+
+        let names: String = sk.iter().map(|job| job.1).collect();
+        assert_eq!(names, "edcabf");
+        sk.sk._check_integrity();
+
+        let mut sk = OrderedSkipList::new();
+        assert_eq!(sk.insert_after_equal(Job(1, 'a')).map(|job| job.1), None);
+        assert_eq!(sk.insert_after_equal(Job(1, 'b')).map(|job| job.1), Some('a'));
+        assert_eq!(sk.len(), 1);
+    }
+
+    #[test]
+    fn value_ranges() {
+        let mut sk = OrderedSkipList::new();
+        for word in ["a", "b", "c", "d"].iter() {
+            sk.insert(word.to_string());
+        }
+        let collect = |range: Range<String>| range.cloned().collect::<Vec<_>>();
+        assert_eq!(collect(sk.range(&"b".to_string()..)), vec!["b", "c", "d"]);
+        assert_eq!(collect(sk.range("b".."d")), vec!["b", "c"]);
+        assert_eq!(collect(sk.range("b"..="d")), vec!["b", "c", "d"]);
+        assert_eq!(collect(sk.range((Bound::Excluded("a"), Bound::Unbounded))), vec!["b", "c", "d"]);
+        assert_eq!(collect(sk.range::<_, str>(..)).len(), 4);
+    }
+
+    #[test]
+    fn get_batch() {
+        let mut sk = OrderedSkipList::new();
+        for i in 0..100 {
+            sk.insert(i * 3);
+        }
+
+        let sorted: Vec<_> = (0..200).collect();
+        let expected: Vec<_> = sorted.iter().map(|q| sk.get_first(q).map(|(_, v)| v)).collect();
+        assert_eq!(sk.get_batch(&sorted), expected);
+
+        let unsorted: Vec<_> = (0..200).map(|i| (i * 37) % 200).collect();
+        let expected: Vec<_> = unsorted.iter().map(|q| sk.get_first(q).map(|(_, v)| v)).collect();
+        assert_eq!(sk.get_batch(&unsorted), expected);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "sorted")]
+    fn from_unsorted_vec_unchecked() {
+        OrderedSkipList::from_sorted_vec_unchecked(vec![1, 3, 2]);
+    }
+
     #[test]
     fn remove_value() {
         let mut sk = OrderedSkipList::new_duplicatable();
@@ -821,4 +2137,78 @@ mod test {
         assert_eq!(sk.len(), 9);
         assert_eq!(sk.get_first(&5), None);
     }
+
+    #[test]
+    fn multiset_operations() {
+        let mut lhs = OrderedSkipList::new_duplicatable();
+        let mut rhs = OrderedSkipList::new_duplicatable();
+        for i in 0..300 {
+            lhs.insert((i * 7) % 23);
+            rhs.insert((i * 11) % 31);
+        }
+
+        let count = |sk: &OrderedSkipList<i32>, v: i32| sk.range(&v..=&v).count();
+        let mut union = vec![];
+        let mut intersection = vec![];
+        for v in 0..31 {
+            let (m, n) = (count(&lhs, v), count(&rhs, v));
+            union.extend(std::iter::repeat(v).take(m.max(n)));
+            intersection.extend(std::iter::repeat(v).take(m.min(n)));
+        }
+        assert_eq!(lhs.union(&rhs).cloned().collect::<Vec<_>>(), union);
+        assert_eq!(
+            lhs.intersection(&rhs).cloned().collect::<Vec<_>>(),
+            intersection
+        );
+        assert_eq!(lhs.merge(&rhs).count(), 600);
+    }
+
+    #[test]
+    fn group_by_key() {
+        let mut sk = OrderedSkipList::new();
+        for i in 0..100 {
+            sk.insert(i);
+        }
+        let clone = OrderedSkipList::from_sorted_vec_unchecked(sk.iter().cloned().collect());
+
+        let groups = sk.group_by_key(|v| v / 10);
+        assert_eq!(groups.len(), 10);
+        assert_eq!(groups.get(&3), Some(&(30..40).collect::<Vec<_>>()));
+
+        // keys are not monotone
+        let mut calls = 0;
+        let groups = clone.group_by_key(|v| {
+            calls += 1;
+            v % 7
+        });
+        assert_eq!(calls, 100);
+        assert_eq!(groups.keys().cloned().collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
+        assert_eq!(groups.get(&3), Some(&(3..100).step_by(7).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn payload_range_mut() {
+        #[derive(PartialEq, Eq, PartialOrd, Ord)]
+        struct Entry(u32, ());
+
+        impl KeyedValue for Entry {
+            type Key = u32;
+            type Payload = ();
+
+            fn split_mut(&mut self) -> (&u32, &mut ()) {
+                (&self.0, &mut self.1)
+            }
+        }
+
+        let mut sk = OrderedSkipList::new();
+        for i in 0..10 {
+            sk.insert(Entry(i, ()));
+        }
+        let keys: Vec<_> = sk
+            .range_mut(&Entry(3, ())..&Entry(6, ()))
+            .map(|(k, _)| *k)
+            .collect();
+        assert_eq!(keys, vec![3, 4, 5]);
+        assert_eq!(sk.iter_mut().count(), 10);
+    }
 }
@@ -0,0 +1,179 @@
+use std::borrow::Borrow;
+
+use crate::level_generator::LevelGenerator;
+use crate::ordered_skiplist::OrderedSkipList;
+use crate::skipmap::SkipMap;
+
+/// A priority queue whose items' priorities can be changed after pushed.
+///
+/// Items are unique, the queue keeps a map from item to its priority, so the
+/// entry of an item in the ordered list can be located by `(priority, item)`.
+/// Like `BinaryHeap`, [`SkipPriorityQueue::pop`] returns the item with the greatest
+/// priority, items with equal priorities are ordered by themselves.
+pub struct SkipPriorityQueue<P: Ord, T: Ord> {
+    queue: OrderedSkipList<(P, T)>,
+    priorities: SkipMap<T, P>,
+}
+
+impl<P: Ord + Clone, T: Ord + Clone> SkipPriorityQueue<P, T> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::new())
+    }
+
+    pub fn with_level_generator(lg: LevelGenerator) -> Self {
+        SkipPriorityQueue {
+            queue: OrderedSkipList::with_config(false, lg.clone()),
+            priorities: SkipMap::with_level_generator(lg),
+        }
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.queue.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.queue.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.queue.set_probability(p);
+        self.priorities.set_probability(p);
+    }
+
+    /// Push an item with its priority, if the item is already in the queue, its
+    /// priority is changed and the old priority is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::priority_queue::SkipPriorityQueue;
+    ///
+    /// let mut pq = SkipPriorityQueue::new();
+    /// pq.push("a", 1);
+    /// pq.push("b", 3);
+    /// assert_eq!(pq.push("a", 5), Some(1));
+    /// assert_eq!(pq.peek(), Some((&"a", &5)));
+    /// ```
+    pub fn push(&mut self, item: T, priority: P) -> Option<P> {
+        let old = self.remove(&item).map(|(p, _)| p);
+        self.queue.insert((priority.clone(), item.clone()));
+        self.priorities.insert(item, priority);
+        old
+    }
+
+    /// Returns the item with the greatest priority
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.queue.back().map(|(p, t)| (t, p))
+    }
+
+    /// Pop the item with the greatest priority
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::priority_queue::SkipPriorityQueue;
+    ///
+    /// let mut pq = SkipPriorityQueue::new();
+    /// pq.push("a", 1);
+    /// pq.push("b", 3);
+    /// pq.push("c", 2);
+    /// assert_eq!(pq.pop(), Some(("b", 3)));
+    /// assert_eq!(pq.pop(), Some(("c", 2)));
+    /// assert_eq!(pq.pop(), Some(("a", 1)));
+    /// assert_eq!(pq.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        let (priority, item) = self.queue.pop_back()?;
+        self.priorities.remove(&item);
+        Some((item, priority))
+    }
+
+    /// Returns the priority of the item
+    pub fn priority<Q: ?Sized>(&self, item: &Q) -> Option<&P>
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        self.priorities.get(item)
+    }
+
+    /// Change priority of the item, returns the old priority, or `None` if the
+    /// item is not in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::priority_queue::SkipPriorityQueue;
+    ///
+    /// let mut pq = SkipPriorityQueue::new();
+    /// pq.push("a", 1);
+    /// pq.push("b", 3);
+    /// assert_eq!(pq.change_priority(&"b", 0), Some(3));
+    /// assert_eq!(pq.change_priority(&"c", 0), None);
+    /// assert_eq!(pq.pop(), Some(("a", 1)));
+    /// ```
+    pub fn change_priority<Q: ?Sized>(&mut self, item: &Q, priority: P) -> Option<P>
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        let (old, item) = self.remove(item)?;
+        self.push(item, priority);
+        Some(old)
+    }
+
+    /// Remove the item from the queue, returns the item and its priority
+    pub fn remove<Q: ?Sized>(&mut self, item: &Q) -> Option<(P, T)>
+    where
+        T: Borrow<Q>,
+        Q: Ord,
+    {
+        let priority = self.priorities.get(item)?;
+        let index = self
+            .queue
+            .sk
+            ._get_first_by(|(p, t)| p.cmp(priority).then_with(|| t.borrow().cmp(item)))
+            .expect("every item in the map is in the queue")
+            .0;
+        self.priorities.remove(item);
+        Some(self.queue.remove(index))
+    }
+
+    /// Returns number of items in the queue
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn change_priority() {
+        let mut pq = SkipPriorityQueue::new();
+        for i in 0..10 {
+            pq.push(i, i * 10);
+        }
+
+        assert_eq!(pq.change_priority(&3, 100), Some(30));
+        assert_eq!(pq.change_priority(&9, 0), Some(90));
+        assert_eq!(pq.priority(&9), Some(&0));
+        assert_eq!(pq.remove(&5), Some((50, 5)));
+        assert_eq!(pq.len(), 9);
+
+        let mut items = vec![];
+        while let Some((item, _)) = pq.pop() {
+            items.push(item);
+        }
+        assert_eq!(items, vec![3, 8, 7, 6, 4, 2, 1, 9, 0]);
+    }
+}
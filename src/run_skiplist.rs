@@ -0,0 +1,627 @@
+use std::borrow::Borrow;
+use std::iter::FusedIterator;
+
+use crate::level_generator::LevelGenerator;
+
+struct RunNode<V> {
+    value: Option<V>,
+    // number of equal values stored in the node
+    count: usize,
+    next: Option<Box<RunNode<V>>>,
+    links: Vec<*mut RunNode<V>>,
+    // number of values between the node (exclusive) and the linked node (inclusive)
+    links_len: Vec<usize>,
+}
+
+impl<V> RunNode<V> {
+    fn new(value: Option<V>, levels: usize) -> Self {
+        RunNode {
+            value,
+            count: 1,
+            next: None,
+            links: vec![std::ptr::null_mut(); levels],
+            links_len: vec![0; levels],
+        }
+    }
+
+    fn increase_level(&mut self) {
+        self.links.push(std::ptr::null_mut());
+        self.links_len.push(0);
+    }
+
+    fn value(&self) -> &V {
+        self.value.as_ref().expect("there must be value in a normal node")
+    }
+}
+
+/// An ordered skiplist for values with heavy duplication, equal values are stored
+/// once in a node with the number of them.
+///
+/// Indexes are counted by values rather than nodes, so a list of a million
+/// copies of a few values takes a few nodes and still supports `get` by index
+/// in O(log n).
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::run_skiplist::RunSkipList;
+///
+/// let mut sk = RunSkipList::new();
+/// for i in 0..1000 {
+///     sk.insert(i % 3);
+/// }
+/// assert_eq!(sk.len(), 1000);
+/// assert_eq!(sk.runs(), 3);
+/// assert_eq!(sk.get(333), Some(&0));
+/// assert_eq!(sk.get(334), Some(&1));
+/// assert_eq!(sk.count(&2), 333);
+/// ```
+pub struct RunSkipList<V: Ord> {
+    head: Box<RunNode<V>>,
+    length: usize,
+    runs: usize,
+    level_generator: LevelGenerator,
+}
+
+unsafe impl<V: Ord + Sync> Sync for RunSkipList<V> {}
+unsafe impl<V: Ord + Send> Send for RunSkipList<V> {}
+
+impl<V: Ord> RunSkipList<V> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::new())
+    }
+
+    pub fn with_level_generator(lg: LevelGenerator) -> Self {
+        RunSkipList {
+            head: Box::new(RunNode::new(None, 1)),
+            length: 0,
+            runs: 0,
+            level_generator: lg,
+        }
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.level_generator.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.level_generator.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.level_generator.set_probability(p)
+    }
+
+    /// Returns number of values
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns number of distinct values, which is the number of nodes
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    /// Insert a value, it's added to the run of equal values if there is one
+    pub fn insert(&mut self, value: V) {
+        let head: *mut RunNode<V> = &mut *self.head;
+        let (mut prevs, mut poses) = Self::_seek(head, &value);
+        self.length += 1;
+
+        if let Some(node_ptr) = Self::_equal_next(&prevs, &value) {
+            // Safety: node_ptr is a valid node returned by _equal_next
+            unsafe { (*node_ptr).count += 1 };
+            Self::_resize_spans(&prevs, 1, false);
+            return;
+        }
+
+        // Only a new node takes a height, so a value joining a run never makes
+        // head higher.
+        let height = self.level_generator.choose() + 1;
+        while prevs.len() < height {
+            // Safety: head is valid, growing its links doesn't move it
+            unsafe { (*head).increase_level() };
+            prevs.push(head);
+            poses.push(0);
+        }
+
+        self.runs += 1;
+        let new_pos = poses[0] + 1;
+        let mut node = Box::new(RunNode::new(Some(value), height));
+        for level in 0..height {
+            // Safety: pointers returned by _seek are valid
+            let prev = unsafe { &*prevs[level] };
+            node.links[level] = prev.links[level];
+            if !prev.links[level].is_null() {
+                node.links_len[level] = poses[level] + prev.links_len[level] + 1 - new_pos;
+            }
+        }
+
+        let node_ptr: *mut RunNode<V> = &mut *node;
+        for (level, &prev_ptr) in prevs.iter().enumerate() {
+            // Safety: pointers returned by _seek are valid
+            let prev = unsafe { &mut *prev_ptr };
+            if level < height {
+                prev.links[level] = node_ptr;
+                prev.links_len[level] = new_pos - poses[level];
+            } else if !prev.links[level].is_null() {
+                prev.links_len[level] += 1;
+            }
+        }
+
+        let prev_ptr = prevs[0];
+        let prev = unsafe { &mut *prev_ptr };
+        node.next = prev.next.take();
+        prev.next = Some(node);
+    }
+
+    /// Remove one value equal to q, returns false if there is no such value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::run_skiplist::RunSkipList;
+    ///
+    /// let mut sk = RunSkipList::new();
+    /// sk.insert(1);
+    /// sk.insert(1);
+    /// assert!(sk.remove(&1));
+    /// assert_eq!(sk.count(&1), 1);
+    /// assert!(sk.remove(&1));
+    /// assert!(!sk.remove(&1));
+    /// ```
+    pub fn remove<Q: ?Sized>(&mut self, q: &Q) -> bool
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let head: *mut RunNode<V> = &mut *self.head;
+        let (prevs, _) = Self::_seek(head, q);
+        let node_ptr = match Self::_equal_next(&prevs, q) {
+            Some(node_ptr) => node_ptr,
+            None => return false,
+        };
+
+        self.length -= 1;
+        // Safety: node_ptr is a valid node returned by _equal_next
+        if unsafe { (*node_ptr).count } > 1 {
+            unsafe { (*node_ptr).count -= 1 };
+            Self::_resize_spans(&prevs, 1, true);
+        } else {
+            self._unlink(&prevs, node_ptr);
+        }
+        true
+    }
+
+    /// Remove all values equal to q, returns the number of them
+    pub fn remove_all<Q: ?Sized>(&mut self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let head: *mut RunNode<V> = &mut *self.head;
+        let (prevs, _) = Self::_seek(head, q);
+        match Self::_equal_next(&prevs, q) {
+            Some(node_ptr) => {
+                let count = self._unlink(&prevs, node_ptr);
+                self.length -= count;
+                count
+            }
+            None => 0,
+        }
+    }
+
+    /// Returns number of values equal to q
+    pub fn count<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let (prevs, _) = Self::_seek(&*self.head as *const _ as *mut _, q);
+        // Safety: node_ptr is a valid node returned by _equal_next
+        Self::_equal_next(&prevs, q).map_or(0, |node_ptr| unsafe { (*node_ptr).count })
+    }
+
+    /// Returns true if there is a value equal to q
+    pub fn contains<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.count(q) > 0
+    }
+
+    /// Returns number of values less than q, which is the index of the first
+    /// value equal to q if it exists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::run_skiplist::RunSkipList;
+    ///
+    /// let mut sk = RunSkipList::new();
+    /// for i in [1, 1, 1, 2, 3].iter() {
+    ///     sk.insert(*i);
+    /// }
+    /// assert_eq!(sk.rank(&2), 3);
+    /// assert_eq!(sk.rank(&4), 5);
+    /// ```
+    pub fn rank<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let (_, poses) = Self::_seek(&*self.head as *const _ as *mut _, q);
+        poses[0]
+    }
+
+    /// Returns value at the given index, or `None` if the index is out of bounds
+    pub fn get(&self, index: usize) -> Option<&V> {
+        if index >= self.length {
+            return None;
+        }
+
+        // the node covering the index is the first one whose position reaches it
+        let target = index + 1;
+        let mut cur_ptr: *const RunNode<V> = &*self.head;
+        let mut cur_pos = 0;
+        for level in (0..self.head.links.len()).rev() {
+            loop {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() || cur_pos + cur.links_len[level] >= target {
+                    break;
+                }
+                cur_pos += cur.links_len[level];
+                cur_ptr = next_ptr;
+            }
+        }
+        // Safety: cur_ptr will never be null and always valid.
+        let cur = unsafe { &*cur_ptr };
+        cur.next.as_deref().map(|node| node.value())
+    }
+
+    /// Returns an iterator of all values, equal values are repeated
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            node: self.head.next.as_deref(),
+            taken: 0,
+            left: self.length,
+        }
+    }
+
+    /// Returns an iterator of distinct values and the number of them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::run_skiplist::RunSkipList;
+    ///
+    /// let mut sk = RunSkipList::new();
+    /// for i in [2, 1, 2].iter() {
+    ///     sk.insert(*i);
+    /// }
+    /// let runs: Vec<_> = sk.iter_runs().collect();
+    /// assert_eq!(runs, vec![(&1, 1), (&2, 2)]);
+    /// ```
+    pub fn iter_runs(&self) -> Runs<'_, V> {
+        Runs {
+            node: self.head.next.as_deref(),
+        }
+    }
+
+    // Returns the last node whose value is less than q and its position in
+    // every level, the position of a node is the number of values up to it
+    fn _seek<Q: ?Sized>(head: *mut RunNode<V>, q: &Q) -> (Vec<*mut RunNode<V>>, Vec<usize>)
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        // Safety: head and nodes linked from it are valid
+        let levels = unsafe { (*head).links.len() };
+        let mut prevs = vec![head; levels];
+        let mut poses = vec![0; levels];
+        let mut cur_ptr = head;
+        let mut cur_pos = 0;
+        for level in (0..levels).rev() {
+            loop {
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() || unsafe { (*next_ptr).value() }.borrow() >= q {
+                    break;
+                }
+                cur_pos += cur.links_len[level];
+                cur_ptr = next_ptr;
+            }
+            prevs[level] = cur_ptr;
+            poses[level] = cur_pos;
+        }
+        (prevs, poses)
+    }
+
+    fn _equal_next<Q: ?Sized>(prevs: &[*mut RunNode<V>], q: &Q) -> Option<*mut RunNode<V>>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        // Safety: pointers returned by _seek are valid
+        let next_ptr = unsafe { (&(*prevs[0]).links)[0] };
+        if !next_ptr.is_null() && unsafe { (*next_ptr).value() }.borrow() == q {
+            Some(next_ptr)
+        } else {
+            None
+        }
+    }
+
+    // Grow or shrink every span over the node right after prevs[0]
+    fn _resize_spans(prevs: &[*mut RunNode<V>], n: usize, shrink: bool) {
+        for (level, &prev_ptr) in prevs.iter().enumerate() {
+            // Safety: pointers returned by _seek are valid
+            let prev = unsafe { &mut *prev_ptr };
+            if prev.links[level].is_null() {
+                continue;
+            }
+            if shrink {
+                prev.links_len[level] -= n;
+            } else {
+                prev.links_len[level] += n;
+            }
+        }
+    }
+
+    // Unlink the node right after prevs[0] and drop it, returns its count
+    fn _unlink(&mut self, prevs: &[*mut RunNode<V>], node_ptr: *mut RunNode<V>) -> usize {
+        // Safety: node_ptr is valid until the node is dropped at the end
+        let node = unsafe { &*node_ptr };
+        let count = node.count;
+        for (level, &prev_ptr) in prevs.iter().enumerate() {
+            // Safety: pointers returned by _seek are valid
+            let prev = unsafe { &mut *prev_ptr };
+            if prev.links[level] == node_ptr {
+                prev.links[level] = node.links[level];
+                prev.links_len[level] = if node.links[level].is_null() {
+                    0
+                } else {
+                    prev.links_len[level] + node.links_len[level] - count
+                };
+            } else if !prev.links[level].is_null() {
+                prev.links_len[level] -= count;
+            }
+        }
+
+        let prev_ptr = prevs[0];
+        let prev = unsafe { &mut *prev_ptr };
+        let mut node = prev.next.take().expect("node to unlink must be the next one");
+        prev.next = node.next.take();
+        self.runs -= 1;
+        count
+    }
+}
+
+impl<V: Ord> Drop for RunSkipList<V> {
+    fn drop(&mut self) {
+        // Drop nodes one by one, otherwise dropping the chain is recursive.
+        let mut next = self.head.next.take();
+        while let Some(mut node) = next {
+            next = node.next.take();
+        }
+    }
+}
+
+impl<V: Ord + std::fmt::Debug> std::fmt::Debug for RunSkipList<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.iter_runs()).finish()
+    }
+}
+
+pub struct Iter<'a, V> {
+    node: Option<&'a RunNode<V>>,
+    // number of values taken from the current node
+    taken: usize,
+    left: usize,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.node?;
+            if self.taken < node.count {
+                self.taken += 1;
+                self.left -= 1;
+                return Some(node.value());
+            }
+            self.node = node.next.as_deref();
+            self.taken = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<'a, V> ExactSizeIterator for Iter<'a, V> {}
+
+impl<'a, V> FusedIterator for Iter<'a, V> {}
+
+pub struct Runs<'a, V> {
+    node: Option<&'a RunNode<V>>,
+}
+
+impl<'a, V> Iterator for Runs<'a, V> {
+    type Item = (&'a V, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node?;
+        self.node = node.next.as_deref();
+        Some((node.value(), node.count))
+    }
+}
+
+impl<'a, V> FusedIterator for Runs<'a, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Check the spans of every level against the counts of the nodes, returns
+    // the height of the highest node
+    fn check_integrity<V: Ord>(sk: &RunSkipList<V>) -> usize {
+        let mut nodes = vec![];
+        let mut node = sk.head.next.as_deref();
+        while let Some(n) = node {
+            nodes.push(n);
+            node = n.next.as_deref();
+        }
+        assert_eq!(nodes.len(), sk.runs());
+        assert_eq!(nodes.iter().map(|n| n.count).sum::<usize>(), sk.len());
+        let height = nodes.iter().map(|n| n.links.len()).max().unwrap_or(0);
+        assert!(sk.head.links.len() >= height);
+
+        for level in 0..sk.head.links.len() {
+            let mut cur: &RunNode<V> = &sk.head;
+            let mut span = 0;
+            for n in nodes.iter() {
+                span += n.count;
+                if n.links.len() <= level {
+                    continue;
+                }
+                assert!(std::ptr::eq(cur.links[level], *n));
+                assert_eq!(cur.links_len[level], span);
+                cur = n;
+                span = 0;
+            }
+            assert!(cur.links[level].is_null());
+        }
+        height
+    }
+
+    #[test]
+    fn same_as_sorted_vec() {
+        let mut sk = RunSkipList::new();
+        let mut expected = vec![];
+        for i in 0..2000 {
+            let value = (i * 7919) % 13;
+            sk.insert(value);
+            expected.push(value);
+            if i % 3 == 0 {
+                let q = (i * 31) % 13;
+                let removed = sk.remove(&q);
+                if let Some(pos) = expected.iter().position(|v| *v == q) {
+                    expected.remove(pos);
+                    assert!(removed);
+                } else {
+                    assert!(!removed);
+                }
+            }
+        }
+        assert_eq!(sk.remove_all(&5), expected.iter().filter(|v| **v == 5).count());
+        expected.retain(|v| *v != 5);
+        expected.sort();
+
+        assert_eq!(sk.len(), expected.len());
+        assert_eq!(sk.runs(), 12);
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(sk.iter().len(), expected.len());
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(value));
+        }
+        assert_eq!(sk.get(expected.len()), None);
+    }
+
+    #[test]
+    fn split_and_merge_runs() {
+        let mut sk = RunSkipList::new();
+        for _ in 0..3 {
+            sk.insert(1);
+            sk.insert(3);
+        }
+        check_integrity(&sk);
+
+        // a new run splits the span between two runs
+        sk.insert(2);
+        sk.insert(2);
+        check_integrity(&sk);
+        assert_eq!(sk.runs(), 3);
+        assert_eq!(sk.rank(&3), 5);
+        assert_eq!(sk.get(3), Some(&2));
+        assert_eq!(sk.get(5), Some(&3));
+
+        // the runs around it meet again once it's gone
+        assert!(sk.remove(&2));
+        check_integrity(&sk);
+        assert_eq!(sk.runs(), 3);
+        assert!(sk.remove(&2));
+        check_integrity(&sk);
+        assert_eq!(sk.runs(), 2);
+        assert_eq!(sk.rank(&3), 3);
+        assert_eq!(sk.get(2), Some(&1));
+        assert_eq!(sk.get(3), Some(&3));
+
+        assert_eq!(sk.remove_all(&1), 3);
+        check_integrity(&sk);
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![3, 3, 3]);
+        assert!(!sk.remove(&1));
+    }
+
+    #[test]
+    fn remove() {
+        let mut sk = RunSkipList::new();
+        for i in 0..500 {
+            sk.insert(i % 50);
+        }
+        for i in 0..500 {
+            assert!(sk.remove(&((i * 7) % 50)));
+            if i % 50 == 0 {
+                check_integrity(&sk);
+            }
+        }
+        check_integrity(&sk);
+        assert_eq!(sk.len(), 0);
+        assert_eq!(sk.runs(), 0);
+        assert_eq!(sk.get(0), None);
+        assert!(!sk.remove(&0));
+    }
+
+    #[test]
+    fn joining_a_run_keeps_height() {
+        let mut sk = RunSkipList::with_level_generator(LevelGenerator::with_propability(0.9));
+        sk.insert(0);
+        let height = sk.head.links.len();
+        for _ in 0..10000 {
+            sk.insert(0);
+        }
+        assert_eq!(sk.head.links.len(), height);
+        assert_eq!(check_integrity(&sk), height);
+
+        // head grows only as high as the new nodes
+        for i in 1..200 {
+            sk.insert(i);
+            assert_eq!(check_integrity(&sk), sk.head.links.len());
+        }
+    }
+
+    #[test]
+    fn level_config() {
+        let mut map = RunSkipList::with_config(0.25, 8);
+        assert_eq!(map.probability(), 0.25);
+        assert_eq!(map.max_levels(), 8);
+        map.set_probability(0.5);
+        assert_eq!(map.probability(), 0.5);
+        for i in 0..100 {
+            map.insert(i % 3);
+        }
+        assert_eq!(map.len(), 100);
+    }
+}
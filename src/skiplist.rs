@@ -1,8 +1,14 @@
 use crate::level_generator::LevelGenerator;
+use crate::metrics::Counters;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::skipmap::SkipMap;
 // use std::fmt::Debug;
 
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
 use std::ops::{Bound, RangeBounds};
+use std::sync::{Mutex, PoisonError};
 
 pub(crate) struct Node<V> {
     pub(crate) value: Option<V>,
@@ -47,10 +53,124 @@ impl<V> Node<V> {
     }
 }
 
+/// Removed nodes kept for later inserts, grouped by their numbers of levels
+pub(crate) struct NodePool<V> {
+    free: Vec<Vec<Box<Node<V>>>>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<V> NodePool<V> {
+    fn new() -> Self {
+        NodePool {
+            free: vec![],
+            len: 0,
+            capacity: 0,
+        }
+    }
+
+    /// Take a node of the levels, its links are reset
+    fn take(&mut self, levels: usize) -> Option<Box<Node<V>>> {
+        let mut node = self.free.get_mut(levels)?.pop()?;
+        self.len -= 1;
+        for i in 0..levels {
+            node.links[i] = std::ptr::null_mut();
+            node.links_len[i] = 0;
+        }
+        Some(node)
+    }
+
+    /// Keep an unlinked node without value if the pool isn't full
+    fn put(&mut self, mut node: Box<Node<V>>) {
+        if self.len >= self.capacity {
+            return;
+        }
+        let levels = node.links.len();
+        if self.free.len() <= levels {
+            self.free.resize_with(levels + 1, Vec::new);
+        }
+        node.next = None;
+        node.prev = std::ptr::null_mut();
+        self.free[levels].push(node);
+        self.len += 1;
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.len > capacity {
+            let nodes = self
+                .free
+                .iter_mut()
+                .rev()
+                .find(|nodes| !nodes.is_empty())
+                .expect("pool is not empty");
+            nodes.pop();
+            self.len -= 1;
+        }
+    }
+}
+
+/// Nodes where the last positional access stopped in every level, see
+/// [`SkipList::set_finger`]
+pub(crate) struct Finger<V> {
+    // the last node in every level whose index (counting head as 0) is not
+    // greater than the accessed one, it's empty if there is no finger
+    path: Vec<(*mut Node<V>, usize)>,
+}
+
+impl<V> Finger<V> {
+    fn new() -> Self {
+        Finger { path: vec![] }
+    }
+
+    fn clear(&mut self) {
+        self.path.clear();
+    }
+
+    /// Returns the lowest level whose node in the finger can be a start of the
+    /// search for the last node not beyond `target` in every level, nodes of
+    /// the finger in that level and above are the results of the search there.
+    ///
+    /// Returns `None` if there is no finger or the head has different levels.
+    fn start(&self, target: usize, levels: usize) -> Option<usize> {
+        if self.path.is_empty() || self.path.len() != levels {
+            return None;
+        }
+
+        // indexes in the path never increase with levels
+        let mut level = self.path.iter().position(|&(_, index)| index <= target)?;
+        while level + 1 < levels {
+            let (ptr, index) = self.path[level];
+            // Safety: nodes in the finger are valid, the finger is cleared or
+            // updated whenever nodes are relinked.
+            let node = unsafe { &*ptr };
+            if node.links[level].is_null() || index + node.links_len[level] > target {
+                break;
+            }
+            level += 1;
+        }
+        Some(level)
+    }
+}
+
+/// How an index range whose end exceeds the length is handled, see
+/// [`SkipList::range_with_policy`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangePolicy {
+    /// The end is clamped to the length, which is what `range` does
+    Clamp,
+    /// The range is rejected
+    Strict,
+}
+
 pub struct SkipList<V> {
     pub(crate) head: Box<Node<V>>,
     pub(crate) length: usize,
     pub(crate) level_generator: LevelGenerator,
+    pub(crate) metrics: Counters,
+    pub(crate) pool: NodePool<V>,
+    finger: Option<Mutex<Finger<V>>>,
+    version: u64,
 }
 
 unsafe impl<V: Sync> Sync for SkipList<V> {}
@@ -58,7 +178,7 @@ unsafe impl<V: Send> Send for SkipList<V> {}
 
 impl<V> SkipList<V> {
     /// Create a skiplist with default LevelGenerator that
-    /// each level's propability is 1/2 of its previous level,
+    /// each level's probability is 1/2 of its previous level,
     /// and less than 32 levels
     ///
     /// # Examples
@@ -77,9 +197,453 @@ impl<V> SkipList<V> {
             head: Box::new(Node::new(None, 0)),
             length: 0,
             level_generator: lg,
+            metrics: Counters::new(),
+            pool: NodePool::new(),
+            finger: None,
+            version: 0,
+        }
+    }
+
+    /// Create a skiplist that each level's probability is `p` of its previous
+    /// level, and less than `max_levels` levels.
+    ///
+    /// A smaller `p` makes towers shorter, which saves links per node but
+    /// makes searches walk more nodes in every level. `max_levels` bounds the
+    /// height of the head, it should be about `log(n) / log(1 / p)` for the
+    /// largest expected length `n`. The ordered containers, maps, sets and
+    /// queues take the same parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `(0, 1)` or `max_levels` is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let sk: SkipList<i64> = SkipList::with_config(0.25, 16);
+    /// assert_eq!(sk.probability(), 0.25);
+    /// assert_eq!(sk.max_levels(), 16);
+    /// ```
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// Returns the probability of a level relative to its previous level
+    pub fn probability(&self) -> f64 {
+        self.level_generator.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.level_generator.max_levels()
+    }
+
+    /// Change the probability of levels, existing nodes keep their levels and
+    /// only nodes inserted afterwards are affected
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.set_probability(0.25);
+    /// sk.push_back(1);
+    /// assert_eq!(sk.probability(), 0.25);
+    /// ```
+    pub fn set_probability(&mut self, p: f64) {
+        self.level_generator.set_probability(p)
+    }
+
+    /// Choose the levels of all nodes again and relink them in one pass, nodes
+    /// and their link buffers are reused. It restores the expected search depth
+    /// after skewed removals, or applies a new probability to existing nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..100 {
+    ///     sk.push_back(i);
+    /// }
+    /// sk.set_probability(0.25);
+    /// sk.rebuild();
+    /// assert_eq!(sk.get(42), Some(&42));
+    /// ```
+    pub fn rebuild(&mut self) {
+        self._bump_version();
+        self._forget_finger();
+        trace_span!("rebuild", len = self.length);
+        let lg = &mut self.level_generator;
+        Self::_link_towers(&mut self.head, || lg.choose() + 1);
+    }
+
+    /// Returns the number of levels of every node in order, which can be given
+    /// back to [`import_levels`](#method.import_levels) with the values to
+    /// reproduce the exact structure, e.g. one that triggered a bug in a fuzzer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.extend(0..100);
+    ///
+    /// let levels = sk.export_levels();
+    /// let copy = SkipList::import_levels(sk.iter().cloned().zip(levels.iter().cloned())).unwrap();
+    /// assert_eq!(copy.export_levels(), levels);
+    /// assert_eq!(copy.get(42), Some(&42));
+    /// ```
+    pub fn export_levels(&self) -> Vec<usize> {
+        let mut levels = Vec::with_capacity(self.length);
+        let mut cur = self.head.next.as_deref();
+        while let Some(node) = cur {
+            levels.push(node.links.len());
+            cur = node.next.as_deref();
+        }
+        levels
+    }
+
+    /// Create a skiplist from values and the numbers of levels of their nodes
+    /// instead of random levels, see [`export_levels`](#method.export_levels).
+    /// Returns an error if a number of levels is 0 or greater than
+    /// [`max_levels`](#method.max_levels) of a new skiplist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let sk = SkipList::import_levels(vec![("a", 1), ("b", 3), ("c", 2)]).unwrap();
+    /// assert_eq!(sk.export_levels(), vec![1, 3, 2]);
+    /// assert_eq!(sk.get(2), Some(&"c"));
+    /// assert!(SkipList::import_levels(vec![("a", 0)]).is_err());
+    /// assert!(SkipList::import_levels(vec![("a", usize::MAX)]).is_err());
+    /// ```
+    pub fn import_levels<I>(iter: I) -> Result<SkipList<V>, &'static str>
+    where
+        I: IntoIterator<Item = (V, usize)>,
+    {
+        let mut sk = SkipList::new();
+        let mut levels = vec![];
+        let mut last_ptr: *mut Node<V> = &mut *sk.head;
+        for (value, level) in iter {
+            if level == 0 {
+                return Err("number of levels should be greater than 0");
+            }
+            if level > sk.max_levels() {
+                return Err("number of levels should not exceed the maximum");
+            }
+            let mut node = Box::new(Node::new(Some(value), 0));
+            node.prev = last_ptr;
+            // Safety: last_ptr is head or the last node pushed, both are alive
+            let last = unsafe { &mut *last_ptr };
+            last_ptr = &mut **last.next.insert(node);
+            levels.push(level);
+        }
+        sk.length = levels.len();
+        let mut levels = levels.into_iter();
+        Self::_link_towers(&mut sk.head, || levels.next().expect("one level per node"));
+        Ok(sk)
+    }
+
+    // Relink every node after head in one pass from the bottom up, `level_of`
+    // returns the number of levels of the next node
+    fn _link_towers<F>(head: &mut Node<V>, mut level_of: F)
+    where
+        F: FnMut() -> usize,
+    {
+        let head_ptr: *mut Node<V> = head;
+        head.links.clear();
+        head.links_len.clear();
+        // the last node and its index in every level
+        let mut prev_ptrs: Vec<*mut Node<V>> = vec![];
+        let mut prev_indexes: Vec<usize> = vec![];
+
+        let mut index = 0;
+        let mut cur_ptr: *mut Node<V> = match head.next.as_deref_mut() {
+            None => std::ptr::null_mut(),
+            Some(node) => node,
+        };
+        while !cur_ptr.is_null() {
+            index += 1;
+            // Safety: cur_ptr is a normal node in the skiplist
+            let cur = unsafe { &mut *cur_ptr };
+            let level = level_of() - 1;
+            cur.links.clear();
+            cur.links.resize(level + 1, std::ptr::null_mut());
+            cur.links_len.clear();
+            cur.links_len.resize(level + 1, 0);
+            while level >= head.links.len() {
+                head.increase_level();
+                prev_ptrs.push(head_ptr);
+                prev_indexes.push(0);
+            }
+
+            for l in 0..=level {
+                // Safety: prev_ptrs are head or nodes before cur
+                let prev = unsafe { &mut *prev_ptrs[l] };
+                prev.links[l] = cur_ptr;
+                prev.links_len[l] = index - prev_indexes[l];
+                prev_ptrs[l] = cur_ptr;
+                prev_indexes[l] = index;
+            }
+
+            cur_ptr = match cur.next.as_deref_mut() {
+                None => std::ptr::null_mut(),
+                Some(node) => node,
+            };
+        }
+    }
+
+    /// Keep at most `capacity` removed nodes and reuse them for later inserts
+    /// whose nodes have the same number of levels, which saves allocations when
+    /// values are inserted and removed at similar rates. Nodes are not kept by
+    /// default.
+    ///
+    /// Nodes are kept by `remove`, `pop_front`, `pop_back`, `remove_range` and
+    /// `clear`, and reused by `insert`, `push_front`, `push_back` and the bulk
+    /// inserts like `extend` and `splice`. Nodes handed out by `drain`,
+    /// `drain_range` and `splice` leave with the returned iterator and are
+    /// dropped with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut window = SkipList::new();
+    /// window.set_node_pool_capacity(16);
+    /// for i in 0..100 {
+    ///     window.push_back(i);
+    ///     if window.len() > 10 {
+    ///         window.pop_front();
+    ///     }
+    /// }
+    /// assert!(window.node_pool_len() <= 16);
+    /// window.set_node_pool_capacity(0);
+    /// assert_eq!(window.node_pool_len(), 0);
+    /// ```
+    pub fn set_node_pool_capacity(&mut self, capacity: usize) {
+        self.pool.set_capacity(capacity)
+    }
+
+    /// Returns number of removed nodes kept for later inserts
+    pub fn node_pool_len(&self) -> usize {
+        self.pool.len
+    }
+
+    /// Remember where the last positional access stopped, so that following
+    /// `get`, `get_mut`, `insert` and `remove` near the same index start from
+    /// there instead of the head. Accesses close to each other, like a cursor
+    /// or a batch of edits in one area, visit fewer nodes, other accesses are
+    /// not slower except for a little bookkeeping.
+    ///
+    /// Splicing, splitting and rebuilding the skiplist forget the finger. It's
+    /// disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.set_finger(true);
+    /// for i in 0..1000 {
+    ///     sk.push_back(i);
+    /// }
+    /// for i in 500..600 {
+    ///     sk.insert(i, 0);
+    ///     assert_eq!(sk.get(i + 1), Some(&500));
+    /// }
+    /// assert!(sk.has_finger());
+    /// ```
+    pub fn set_finger(&mut self, enabled: bool) {
+        if !enabled {
+            self.finger = None;
+        } else if self.finger.is_none() {
+            self.finger = Some(Mutex::new(Finger::new()));
+        }
+    }
+
+    /// Returns whether the skiplist remembers the last positional access
+    pub fn has_finger(&self) -> bool {
+        self.finger.is_some()
+    }
+
+    /// Forget the finger, it must be called when nodes are relinked other than
+    /// by `insert` and `remove`
+    pub(crate) fn _forget_finger(&mut self) {
+        if let Some(finger) = self.finger.as_mut() {
+            finger
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+    }
+
+    /// Returns a number that changes whenever values are inserted, removed,
+    /// moved or borrowed mutably, see [`iter_versioned`](#method.iter_versioned)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// let version = sk.version();
+    /// sk.push_back(0);
+    /// assert_ne!(sk.version(), version);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub(crate) fn _bump_version(&mut self) {
+        self.version = self.version.wrapping_add(1);
+    }
+
+    /// Returns an iterator that can be paused by taking a token of its position,
+    /// and resumed with [`resume_iter`](#method.resume_iter) later. Resuming
+    /// fails if the skiplist is changed in between.
+    ///
+    /// It's for iterating in chunks a skiplist shared behind a `RwLock` or a
+    /// `RefCell`, the lock only has to be held while a chunk is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::RwLock;
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.extend_front(0..10);
+    /// let shared = RwLock::new(sk);
+    ///
+    /// let token = {
+    ///     let sk = shared.read().unwrap();
+    ///     let mut it = sk.iter_versioned();
+    ///     assert_eq!(it.by_ref().take(5).sum::<i32>(), 10);
+    ///     it.token()
+    /// };
+    /// {
+    ///     let sk = shared.read().unwrap();
+    ///     let rest = sk.resume_iter(token).unwrap();
+    ///     assert_eq!(rest.sum::<i32>(), 35);
+    /// }
+    ///
+    /// shared.write().unwrap().push_back(10);
+    /// assert!(shared.read().unwrap().resume_iter(token).is_err());
+    /// ```
+    pub fn iter_versioned(&self) -> VersionedIter<'_, V> {
+        VersionedIter {
+            inner: self.range(..),
+            version: self.version,
+            index: 0,
+        }
+    }
+
+    /// Resume iteration from the token of a [`VersionedIter`] of this
+    /// skiplist, returns an error if the skiplist is changed since the token
+    /// was taken. A token of another skiplist is only caught if its position
+    /// is out of bounds of this one, tokens shouldn't be passed between lists.
+    pub fn resume_iter(&self, token: IterToken) -> Result<VersionedIter<'_, V>, &'static str> {
+        if token.version != self.version {
+            return Err("The skiplist is changed since the token was taken");
+        }
+        if token.index > self.length {
+            return Err("The token is out of bounds of the skiplist");
+        }
+        Ok(VersionedIter {
+            inner: self.range(token.index..),
+            version: self.version,
+            index: token.index,
+        })
+    }
+
+    /// Create a node of the value, a node in the pool is reused if there is one
+    /// with the same number of levels
+    pub(crate) fn _new_node(&mut self, value: V, levels: usize) -> Box<Node<V>> {
+        match self.pool.take(levels) {
+            Some(mut node) => {
+                node.value = Some(value);
+                node
+            }
+            None => Box::new(Node::new(Some(value), levels)),
         }
     }
 
+    /// Move the value out of an unlinked node and put the node in the pool
+    pub(crate) fn _recycle_node(&mut self, mut node: Box<Node<V>>) -> V {
+        let value = node.value.take().expect("there must be value in a normal node");
+        self.pool.put(node);
+        value
+    }
+
+    /// Returns numbers of operations since the skiplist is created or its
+    /// metrics are reset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(1);
+    /// sk.push_back(2);
+    /// sk.remove(0);
+    /// let metrics = sk.metrics();
+    /// assert_eq!((metrics.inserts, metrics.removes), (2, 1));
+    ///
+    /// sk.reset_metrics();
+    /// assert_eq!(sk.metrics(), Default::default());
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.snapshot()
+    }
+
+    /// Returns numbers of nodes moved to in every level since the skiplist is
+    /// created or its metrics are reset, from the bottom level up. Unlike wall
+    /// clock time they are stable enough to assert on, e.g. to catch a bad level
+    /// distribution in tests and benchmarks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.extend(0..1000);
+    /// sk.reset_metrics();
+    /// for i in 0..1000 {
+    ///     sk.get(i);
+    /// }
+    /// let visits = sk.level_visits();
+    /// assert_eq!(visits.iter().sum::<u64>(), sk.metrics().nodes_visited);
+    /// assert!(visits[0] > 0);
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn level_visits(&self) -> Vec<u64> {
+        self.metrics.level_visits(self.head.links.len())
+    }
+
+    /// Reset all metrics to 0
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&self) {
+        self.metrics.reset()
+    }
+
     /// Insert value at specific index
     ///
     /// # Panics
@@ -100,21 +664,37 @@ impl<V> SkipList<V> {
         if index > self.length {
             panic!("Index out of bounds.");
         }
+        self._bump_version();
 
         let level = self.level_generator.choose();
-        let mut node = Box::new(Node::new(Some(value), level + 1));
+        let mut node = self._new_node(value, level + 1);
         let node_ptr: *mut _ = &mut *node;
         while level >= self.head.links.len() {
             self.head.increase_level();
         }
 
+        let levels = self.head.links.len();
         let mut cur_index = 0;
-        let mut cur_level = self.head.links.len() - 1;
+        let mut cur_level = levels - 1;
         let mut cur_ptr: *mut _ = &mut *self.head;
         // Outsider doesn't know the existence of head, but we should consider head
         // as the first node while inserting, so the index should be added by 1.
         let actual_index = index + 1;
 
+        let mut finger = self
+            .finger
+            .as_mut()
+            .map(|f| f.get_mut().unwrap_or_else(PoisonError::into_inner));
+        let start_level = finger.as_ref().and_then(|f| f.start(actual_index - 1, levels));
+        if let Some(f) = finger.as_mut() {
+            if start_level.is_some() {
+                let (ptr, index) = f.path[cur_level];
+                cur_ptr = ptr;
+                cur_index = index;
+            }
+            f.path.resize(levels, (std::ptr::null_mut(), 0));
+        }
+
         loop {
             // Safety: cur_ptr will never be null and always valid.
             let cur = unsafe { &mut *cur_ptr };
@@ -124,30 +704,35 @@ impl<V> SkipList<V> {
                     cur.links[cur_level] = node_ptr;
                     cur.links_len[cur_level] = actual_index - cur_index;
                 }
-                if cur_level == 0 {
-                    break;
+            } else {
+                let next_index = cur_index + cur.links_len[cur_level];
+                if next_index < actual_index {
+                    // move forward in the same level
+                    cur_ptr = cur.links[cur_level];
+                    self.metrics.visit(cur_level);
+                    cur_index = next_index;
+                    continue;
                 }
-                cur_level -= 1;
-                continue;
-            }
 
-            let next_index = cur_index + cur.links_len[cur_level];
-            if next_index < actual_index {
-                // move forward in the same level
-                cur_ptr = cur.links[cur_level];
-                cur_index = next_index;
-                continue;
+                if cur_level <= level {
+                    // insert link between current node and the next node
+                    node.links_len[cur_level] = next_index + 1 - actual_index;
+                    cur.links_len[cur_level] = actual_index - cur_index;
+                    node.links[cur_level] = cur.links[cur_level];
+                    cur.links[cur_level] = node_ptr;
+                } else {
+                    // increase link_len between current node and the next node
+                    cur.links_len[cur_level] += 1;
+                }
             }
 
-            if cur_level <= level {
-                // insert link between current node and the next node
-                node.links_len[cur_level] = next_index + 1 - actual_index;
-                cur.links_len[cur_level] = actual_index - cur_index;
-                node.links[cur_level] = cur.links[cur_level];
-                cur.links[cur_level] = node_ptr;
-            } else {
-                // increase link_len between current node and the next node
-                cur.links_len[cur_level] += 1;
+            if let Some(f) = finger.as_mut() {
+                // the new node is the last one not beyond itself in its levels
+                f.path[cur_level] = if cur_level <= level {
+                    (node_ptr, actual_index)
+                } else {
+                    (cur_ptr, cur_index)
+                };
             }
 
             if cur_level == 0 {
@@ -155,6 +740,13 @@ impl<V> SkipList<V> {
             }
 
             cur_level -= 1;
+            if matches!(start_level, Some(start) if cur_level >= start) {
+                if let Some(f) = finger.as_ref() {
+                    let (ptr, index) = f.path[cur_level];
+                    cur_ptr = ptr;
+                    cur_index = index;
+                }
+            }
         }
 
         // Safety: cur_ptr will never be null and always valid.
@@ -172,15 +764,448 @@ impl<V> SkipList<V> {
         };
 
         self.length += 1;
+        self.metrics.insert(1);
     }
 
-    /// Remove item at specific index
-    ///
-    /// # Panics
+    /// Insert values of the iterator before the first element, values keep their
+    /// order in the iterator. All values are linked in one splice.
     ///
-    /// Panics if index exceeds the length of the skiplist
-    ///
-    /// # Example
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(3);
+    /// sk.push_back(4);
+    /// sk.extend_front(vec![0, 1, 2]);
+    ///
+    /// let values: Vec<_> = sk.iter().cloned().collect();
+    /// assert_eq!(values, vec![0, 1, 2, 3, 4]);
+    /// ```
+    pub fn extend_front<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = V>,
+    {
+        self._insert_iter_at(0, iter);
+    }
+
+    /// Insert values of the iterator at the index in one splice, returns number of
+    /// inserted values.
+    ///
+    /// Nodes of the values are created and linked to each other first, then the
+    /// whole segment is linked into the skiplist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
+    pub(crate) fn _insert_iter_at<I>(&mut self, index: usize, iter: I) -> usize
+    where
+        I: IntoIterator<Item = V>,
+    {
+        if index > self.length {
+            panic!("Index out of bounds.");
+        }
+        self._bump_version();
+
+        trace_span!("insert_iter", len = self.length, index);
+        self._forget_finger();
+        let mut nodes: Vec<Box<Node<V>>> = iter
+            .into_iter()
+            .map(|value| {
+                let level = self.level_generator.choose();
+                self._new_node(value, level + 1)
+            })
+            .collect();
+        let count = nodes.len();
+        trace_event!(count, "nodes are built");
+        if count == 0 {
+            return 0;
+        }
+
+        let max_levels = nodes.iter().map(|node| node.links.len()).max().unwrap_or(1);
+        while self.head.links.len() < max_levels {
+            self.head.increase_level();
+        }
+
+        // get previous nodes of the position in every level
+        let total_level = self.head.links.len();
+        let actual_index = index + 1;
+        let mut prev_ptrs = vec![std::ptr::null_mut(); total_level];
+        let mut prev_indexes = vec![0; total_level];
+        let mut cur_ptr: *mut Node<V> = &mut *self.head;
+        let mut cur_index = 0;
+        for level in (0..total_level).rev() {
+            loop {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() || cur_index + cur.links_len[level] >= actual_index {
+                    break;
+                }
+                cur_index += cur.links_len[level];
+                cur_ptr = next_ptr;
+                self.metrics.visit(level);
+            }
+            prev_ptrs[level] = cur_ptr;
+            prev_indexes[level] = cur_index;
+        }
+
+        // modify links level by level
+        let node_ptrs: Vec<*mut Node<V>> = nodes.iter_mut().map(|node| &mut **node as *mut _).collect();
+        for level in 0..total_level {
+            let (old_next, old_next_index) = {
+                // Safety: prev_ptrs[level] is copy from cur_ptr above, will never
+                // be null and always valid.
+                let prev = unsafe { &*prev_ptrs[level] };
+                (prev.links[level], prev_indexes[level] + prev.links_len[level] + count)
+            };
+
+            let mut last_ptr = prev_ptrs[level];
+            let mut last_index = prev_indexes[level];
+            for (offset, &node_ptr) in node_ptrs.iter().enumerate() {
+                // Safety: node_ptr points to a node in `nodes`
+                if unsafe { &*node_ptr }.links.len() <= level {
+                    continue;
+                }
+                // Safety: last_ptr is either a previous node or a node in `nodes`
+                let last = unsafe { &mut *last_ptr };
+                last.links[level] = node_ptr;
+                last.links_len[level] = actual_index + offset - last_index;
+                last_ptr = node_ptr;
+                last_index = actual_index + offset;
+            }
+
+            // Safety: last_ptr is either a previous node or a node in `nodes`
+            let last = unsafe { &mut *last_ptr };
+            last.links[level] = old_next;
+            last.links_len[level] = if old_next.is_null() {
+                0
+            } else {
+                old_next_index - last_index
+            };
+        }
+
+        // link nodes in level 0
+        // Safety: prev_ptrs[0] is copy from cur_ptr above, will never be null
+        // and always valid.
+        let prev = unsafe { &mut *prev_ptrs[0] };
+        let mut rest = prev.next.take();
+        if let Some(next) = rest.as_mut() {
+            next.prev = node_ptrs[count - 1];
+        }
+        for (i, mut node) in nodes.into_iter().enumerate().rev() {
+            node.prev = if i == 0 { prev_ptrs[0] } else { node_ptrs[i - 1] };
+            node.next = rest;
+            rest = Some(node);
+        }
+        prev.next = rest;
+
+        self.length += count;
+        self.metrics.insert(count);
+        count
+    }
+
+    /// Insert value right after the node at `prev_ptr` whose actual index (head is 0)
+    /// is `prev_index`, returns pointer to the new node.
+    ///
+    /// The node at `prev_ptr` is the previous node in all of its levels, only the
+    /// levels higher than it are searched from head to lengthen links over the
+    /// new node.
+    pub(crate) fn _insert_after_ptr(&mut self, prev_ptr: *mut Node<V>, prev_index: usize, value: V) -> *mut Node<V> {
+        self._bump_version();
+        self._forget_finger();
+        let level = self.level_generator.choose();
+        let mut node = self._new_node(value, level + 1);
+        let node_ptr: *mut _ = &mut *node;
+        while level >= self.head.links.len() {
+            self.head.increase_level();
+        }
+
+        // Safety: prev_ptr is a node in the skiplist given by the caller
+        let known_levels = unsafe { &*prev_ptr }.links.len();
+        let actual_index = prev_index + 1;
+        let mut cur_ptr: *mut Node<V> = &mut *self.head;
+        let mut cur_index = 0;
+        for cur_level in (0..self.head.links.len()).rev() {
+            if cur_level < known_levels {
+                cur_ptr = prev_ptr;
+                cur_index = prev_index;
+            } else {
+                loop {
+                    // Safety: cur_ptr will never be null and always valid.
+                    let cur = unsafe { &*cur_ptr };
+                    let next_ptr = cur.links[cur_level];
+                    if next_ptr.is_null() || cur_index + cur.links_len[cur_level] >= actual_index {
+                        break;
+                    }
+                    cur_index += cur.links_len[cur_level];
+                    cur_ptr = next_ptr;
+                    self.metrics.visit(cur_level);
+                }
+            }
+
+            // Safety: cur_ptr will never be null and always valid.
+            let cur = unsafe { &mut *cur_ptr };
+            if cur_level <= level {
+                // insert link between current node and the next node
+                if !cur.links[cur_level].is_null() {
+                    let next_index = cur_index + cur.links_len[cur_level];
+                    node.links_len[cur_level] = next_index + 1 - actual_index;
+                }
+                node.links[cur_level] = cur.links[cur_level];
+                cur.links[cur_level] = node_ptr;
+                cur.links_len[cur_level] = actual_index - cur_index;
+            } else if !cur.links[cur_level].is_null() {
+                // increase link_len between current node and the next node
+                cur.links_len[cur_level] += 1;
+            }
+        }
+
+        // Safety: prev_ptr is a node in the skiplist given by the caller
+        let pre_node = unsafe { &mut *prev_ptr };
+        node.prev = prev_ptr;
+        if let Some(next) = pre_node.next.as_mut() {
+            next.prev = node_ptr;
+        }
+        node.next = pre_node.next.take();
+        pre_node.next = Some(node);
+
+        self.length += 1;
+        self.metrics.insert(1);
+        node_ptr
+    }
+
+    /// Returns a cursor pointing to the element at the index, values can be inserted
+    /// beside the cursor without searching the position from the head again. The
+    /// cursor is at the ghost position if the index is the length, so a cursor can
+    /// start on an empty skiplist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(1);
+    /// sk.push_back(3);
+    ///
+    /// let mut cursor = sk.cursor_mut(0);
+    /// cursor.insert_after(2);
+    /// cursor.insert_before(0);
+    /// assert_eq!(cursor.index(), Some(1));
+    ///
+    /// let values: Vec<_> = sk.iter().cloned().collect();
+    /// assert_eq!(values, vec![0, 1, 2, 3]);
+    ///
+    /// let mut empty = SkipList::new();
+    /// let mut cursor = empty.cursor_mut(0);
+    /// assert_eq!(cursor.index(), None);
+    /// cursor.insert_after(0);
+    /// assert_eq!(empty.get(0), Some(&0));
+    /// ```
+    pub fn cursor_mut(&mut self, index: usize) -> CursorMut<'_, V> {
+        if index == self.length {
+            let current: *mut Node<V> = &mut *self.head;
+            return CursorMut {
+                list: self,
+                current,
+                index: 0,
+            };
+        }
+        let current = self._get_ptr(index) as *mut Node<V>;
+        CursorMut {
+            list: self,
+            current,
+            index: index + 1,
+        }
+    }
+
+    /// Insert value at the index, returns a handle of the element that gives O(1)
+    /// access to it and its neighbors later.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// let handle = sk.insert_with_handle(1, 1);
+    /// sk.push_front(-1);
+    ///
+    /// // Safety: the element of the handle is still in the skiplist
+    /// unsafe {
+    ///     assert_eq!(sk.get_by_handle(handle), &1);
+    ///     assert_eq!(sk.index_of_handle(handle), 2);
+    ///     let prev = sk.prev_handle(handle).unwrap();
+    ///     assert_eq!(sk.get_by_handle(prev), &0);
+    ///     assert_eq!(sk.remove_by_handle(handle), 1);
+    /// }
+    /// assert_eq!(sk.len(), 2);
+    /// ```
+    pub fn insert_with_handle(&mut self, index: usize, value: V) -> NodeRef<V> {
+        if index > self.length {
+            panic!("Index out of bounds.");
+        }
+
+        let prev_ptr = if index == 0 {
+            &mut *self.head as *mut Node<V>
+        } else {
+            self._get_ptr(index - 1) as *mut Node<V>
+        };
+        NodeRef {
+            ptr: self._insert_after_ptr(prev_ptr, index, value),
+        }
+    }
+
+    /// Insert value right after the handle's element, returns a handle of the new
+    /// element. The handle's element is the previous node in all of its levels, so
+    /// only higher levels are searched, and its index is found in O(log n) from
+    /// the handle.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// let handle = sk.insert_with_handle(0, 1);
+    ///
+    /// // Safety: the element of the handle is still in the skiplist
+    /// unsafe {
+    ///     let next = sk.insert_after_handle(handle, 3);
+    ///     sk.insert_before_handle(next, 2);
+    ///     sk.insert_before_handle(handle, 0);
+    ///     assert_eq!(sk.index_of_handle(next), 3);
+    /// }
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// ```
+    pub unsafe fn insert_after_handle(&mut self, handle: NodeRef<V>, value: V) -> NodeRef<V> {
+        let prev_index = self.index_of_handle(handle) + 1;
+        NodeRef {
+            ptr: self._insert_after_ptr(handle.ptr, prev_index, value),
+        }
+    }
+
+    /// Insert value right before the handle's element, returns a handle of the
+    /// new element. See [`insert_after_handle`](#method.insert_after_handle).
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn insert_before_handle(&mut self, handle: NodeRef<V>, value: V) -> NodeRef<V> {
+        let prev_index = self.index_of_handle(handle);
+        let prev_ptr = (*handle.ptr).prev;
+        NodeRef {
+            ptr: self._insert_after_ptr(prev_ptr, prev_index, value),
+        }
+    }
+
+    /// Returns value of the handle
+    ///
+    /// # Safety
+    ///
+    /// The handle must be returned by this skiplist and its element must not be
+    /// removed, the same applies to all methods taking a handle.
+    pub unsafe fn get_by_handle(&self, handle: NodeRef<V>) -> &V {
+        (*handle.ptr).value.as_ref().expect("there must be value in a normal node")
+    }
+
+    /// Returns mutable value of the handle
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn get_mut_by_handle(&mut self, handle: NodeRef<V>) -> &mut V {
+        self._bump_version();
+        (*handle.ptr).value.as_mut().expect("there must be value in a normal node")
+    }
+
+    /// Returns handle of the next element, or `None` if it's the last one
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn next_handle(&self, handle: NodeRef<V>) -> Option<NodeRef<V>> {
+        (*handle.ptr).next.as_deref_mut().map(|next| NodeRef { ptr: next })
+    }
+
+    /// Returns handle of the previous element, or `None` if it's the first one
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn prev_handle(&self, handle: NodeRef<V>) -> Option<NodeRef<V>> {
+        let prev_ptr = (*handle.ptr).prev;
+        if std::ptr::eq(prev_ptr, &*self.head) {
+            return None;
+        }
+        Some(NodeRef { ptr: prev_ptr })
+    }
+
+    /// Returns index of the handle's element in O(log n)
+    ///
+    /// It's counted from the distance to the last element: the walk climbs up by
+    /// the highest link of every node, then goes down to the last node.
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn index_of_handle(&self, handle: NodeRef<V>) -> usize {
+        let mut cur_ptr = handle.ptr as *const Node<V>;
+        let mut distance = 0;
+        loop {
+            let cur = &*cur_ptr;
+            let top = cur.links.len() - 1;
+            if cur.links[top].is_null() {
+                break;
+            }
+            distance += cur.links_len[top];
+            cur_ptr = cur.links[top];
+        }
+        for level in (0..(*cur_ptr).links.len()).rev() {
+            loop {
+                let cur = &*cur_ptr;
+                if cur.links[level].is_null() {
+                    break;
+                }
+                distance += cur.links_len[level];
+                cur_ptr = cur.links[level];
+            }
+        }
+        self.length - 1 - distance
+    }
+
+    /// Remove the handle's element in O(log n), returns its value
+    ///
+    /// # Safety
+    ///
+    /// See [`get_by_handle`](#method.get_by_handle)
+    pub unsafe fn remove_by_handle(&mut self, handle: NodeRef<V>) -> V {
+        let index = self.index_of_handle(handle);
+        self.remove(index)
+    }
+
+    /// Remove item at specific index
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
+    ///
+    /// # Example
     ///
     /// ```
     /// use skiplist::skiplist::SkipList;
@@ -196,12 +1221,28 @@ impl<V> SkipList<V> {
         if index > self.length {
             panic!("Index out of bounds.");
         }
+        self._bump_version();
 
         let actual_index = index + 1;
+        let levels = self.head.links.len();
         let mut cur_index = 0;
-        let mut cur_level = self.head.links.len() - 1;
+        let mut cur_level = levels - 1;
         let mut cur_ptr: *mut _ = &mut *self.head;
 
+        let mut finger = self
+            .finger
+            .as_mut()
+            .map(|f| f.get_mut().unwrap_or_else(PoisonError::into_inner));
+        let start_level = finger.as_ref().and_then(|f| f.start(actual_index - 1, levels));
+        if let Some(f) = finger.as_mut() {
+            if start_level.is_some() {
+                let (ptr, index) = f.path[cur_level];
+                cur_ptr = ptr;
+                cur_index = index;
+            }
+            f.path.resize(levels, (std::ptr::null_mut(), 0));
+        }
+
         loop {
             // Safety: cur_ptr will never be null and always valid.
             let cur = unsafe { &mut *cur_ptr };
@@ -210,35 +1251,39 @@ impl<V> SkipList<V> {
                 if cur_level == 0 {
                     unreachable!()
                 }
-                cur_level -= 1;
-                continue;
-            }
-
-            // Safety: next_ptr won't be null when the program run to here and always valid.
-            let next = unsafe { &*next_ptr };
-            let next_index = cur_index + cur.links_len[cur_level];
-            let next_links_len = next.links_len[cur_level];
+            } else {
+                // Safety: next_ptr is checked that it won't be null and always valid.
+                let next = unsafe { &*next_ptr };
+                let next_index = cur_index + cur.links_len[cur_level];
+                let next_links_len = next.links_len[cur_level];
+
+                if next_index < actual_index {
+                    // move forward in the same level
+                    cur_ptr = cur.links[cur_level];
+                    self.metrics.visit(cur_level);
+                    cur_index = next_index;
+                    continue;
+                }
 
-            if next_index < actual_index {
-                // move forward in the same level
-                cur_ptr = cur.links[cur_level];
-                cur_index = next_index;
-                continue;
-            }
+                if next_index == actual_index {
+                    // remove next link
+                    cur.links[cur_level] = next.links[cur_level];
+                    if next_links_len == 0 {
+                        cur.links_len[cur_level] = 0;
+                    } else {
+                        cur.links_len[cur_level] += next_links_len - 1;
+                    }
+                }
 
-            if next_index == actual_index {
-                // remove next link
-                cur.links[cur_level] = next.links[cur_level];
-                if next_links_len == 0 {
-                    cur.links_len[cur_level] = 0;
-                } else {
-                    cur.links_len[cur_level] += next_links_len - 1;
+                if next_index > actual_index {
+                    // decrease link_len between current node and the next node
+                    cur.links_len[cur_level] -= 1;
                 }
             }
 
-            if next_index > actual_index {
-                // decrease link_len between current node and the next node
-                cur.links_len[cur_level] -= 1;
+            // nodes before the removed one are the finger of the index before it
+            if let Some(f) = finger.as_mut() {
+                f.path[cur_level] = (cur_ptr, cur_index);
             }
 
             if cur_level == 0 {
@@ -246,6 +1291,13 @@ impl<V> SkipList<V> {
             }
 
             cur_level -= 1;
+            if matches!(start_level, Some(start) if cur_level >= start) {
+                if let Some(f) = finger.as_ref() {
+                    let (ptr, index) = f.path[cur_level];
+                    cur_ptr = ptr;
+                    cur_index = index;
+                }
+            }
         }
 
         // Safety: cur_ptr will never be null and always valid.
@@ -263,10 +1315,9 @@ impl<V> SkipList<V> {
         };
 
         self.length -= 1;
+        self.metrics.remove(1);
 
-        the_node
-            .value
-            .expect("there must be value in a normal node")
+        self._recycle_node(the_node)
     }
 
     /// Remove items in a range of indexes
@@ -294,39 +1345,235 @@ impl<V> SkipList<V> {
         R: RangeBounds<usize>,
     {
         let (left, right) = self._normalize_range(range);
-        if left == right {
-            return 0;
-        }
-
-        // convert to actual index
-        let (left, right) = (left + 1, right + 1);
+        trace_span!("remove_range", len = self.length, removed = right - left);
+        let mut detached = self._unlink_range(left, right);
 
-        let total_level = self.head.links.len();
+        // Recycle nodes one by one, otherwise dropping the chain is recursive.
+        while let Some(mut node) = detached {
+            detached = node.next.take();
+            self._recycle_node(node);
+        }
 
-        let mut prev_ptrs = vec![std::ptr::null_mut(); total_level];
-        let mut prev_indexes = vec![0; total_level];
-        let mut cur_level = total_level - 1;
-        let mut cur_ptr: *mut _ = &mut *self.head;
-        let mut cur_index = 0;
+        right - left
+    }
 
-        loop {
-            prev_ptrs[cur_level] = cur_ptr;
-            prev_indexes[cur_level] = cur_index;
+    /// Remove items in a range of indexes and return them in an iterator. The
+    /// range is unlinked in one pass when this is called, values not consumed
+    /// by the iterator are dropped with it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bounds is greater than end_bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let window: Vec<_> = sk.drain_range(3..6).collect();
+    /// assert_eq!(window, vec![3, 4, 5]);
+    /// assert_eq!(sk.len(), 7);
+    /// assert_eq!(sk.get(3), Some(&6));
+    /// ```
+    pub fn drain_range<R>(&mut self, range: R) -> Drain<V>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (left, right) = self._normalize_range(range);
+        trace_span!("drain_range", len = self.length, removed = right - left);
+        let first = self._unlink_range(left, right);
+        Drain {
+            first,
+            len: right - left,
+        }
+    }
 
-            // Safety: cur_ptr will never be null and always valid.
-            let next_ptr = unsafe { (*cur_ptr).links[cur_level] };
-            if next_ptr.is_null() {
-                if cur_level == 0 {
-                    break;
+    /// Replace the range with values of the iterator, returns the removed
+    /// values. The range is unlinked in one pass and the new values are linked
+    /// in one splice, both happen before this returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bounds is greater than end_bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..6 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let removed: Vec<_> = sk.splice(1..4, vec![10, 20]).collect();
+    /// assert_eq!(removed, vec![1, 2, 3]);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 10, 20, 4, 5]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Drain<V>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = V>,
+    {
+        let (left, right) = self._normalize_range(range);
+        trace_span!("splice", len = self.length, removed = right - left);
+        let removed = Drain {
+            first: self._unlink_range(left, right),
+            len: right - left,
+        };
+        self._insert_iter_at(left, replace_with);
+        removed
+    }
+
+    /// Remove all values, the head is kept for reuse and its levels are
+    /// dropped, so the skiplist is the same as a new one afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for batch in 0..3 {
+    ///     for i in 0..100 {
+    ///         sk.push_back(batch * 100 + i);
+    ///     }
+    ///     assert_eq!(sk.get(0), Some(&(batch * 100)));
+    ///     sk.clear();
+    ///     assert_eq!(sk.len(), 0);
+    /// }
+    /// ```
+    pub fn clear(&mut self) {
+        trace_span!("clear", len = self.length);
+        let mut detached = self.drain().first.take();
+        // Recycle nodes one by one, otherwise dropping the chain is recursive.
+        while let Some(mut node) = detached {
+            detached = node.next.take();
+            self._recycle_node(node);
+        }
+        self.head.links.clear();
+        self.head.links_len.clear();
+    }
+
+    /// Returns an iterator that removes and yields values matching the predicate
+    /// in one pass from the front, values that don't match are kept in place.
+    ///
+    /// Values after the last one yielded are not tested if the iterator is
+    /// dropped early, they are kept too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let evens: Vec<_> = sk.extract_if(|v| *v % 2 == 0).collect();
+    /// assert_eq!(evens, vec![0, 2, 4, 6, 8]);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, V, F>
+    where
+        F: FnMut(&mut V) -> bool,
+    {
+        self._bump_version();
+        trace_span!("extract_if", len = self.length);
+        self._forget_finger();
+        let head: *mut Node<V> = &mut *self.head;
+        ExtractIf {
+            kept: vec![head; self.head.links.len()],
+            sk: self,
+            pred,
+        }
+    }
+
+    /// Remove all values and return them in an iterator, the skiplist is left
+    /// empty and can be reused. Values not consumed by the iterator are dropped
+    /// with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..5 {
+    ///     sk.push_back(i.to_string());
+    /// }
+    ///
+    /// let values: Vec<String> = sk.drain().collect();
+    /// assert_eq!(values, vec!["0", "1", "2", "3", "4"]);
+    /// assert_eq!(sk.len(), 0);
+    /// sk.push_back("5".to_string());
+    /// assert_eq!(sk.get(0).map(|s| s.as_str()), Some("5"));
+    /// ```
+    pub fn drain(&mut self) -> Drain<V> {
+        self._bump_version();
+        self._forget_finger();
+        let len = self.length;
+        let mut first = self.head.next.take();
+        if let Some(node) = first.as_mut() {
+            node.prev = std::ptr::null_mut();
+        }
+        for i in 0..self.head.links.len() {
+            self.head.links[i] = std::ptr::null_mut();
+            self.head.links_len[i] = 0;
+        }
+        self.length = 0;
+        self.metrics.remove(len);
+        Drain { first, len }
+    }
+
+    /// Unlink nodes in [left, right) from the skiplist, returns the first unlinked
+    /// node which owns the rest of the unlinked nodes through `next`.
+    ///
+    /// Caller should take nodes out of the chain one by one, dropping the chain
+    /// directly is recursive.
+    pub(crate) fn _unlink_range(&mut self, left: usize, right: usize) -> Option<Box<Node<V>>> {
+        if left == right {
+            return None;
+        }
+
+        self._bump_version();
+        self._forget_finger();
+        // convert to actual index
+        let (left, right) = (left + 1, right + 1);
+
+        let total_level = self.head.links.len();
+
+        let mut prev_ptrs = vec![std::ptr::null_mut(); total_level];
+        let mut prev_indexes = vec![0; total_level];
+        let mut cur_level = total_level - 1;
+        let mut cur_ptr: *mut _ = &mut *self.head;
+        let mut cur_index = 0;
+
+        loop {
+            prev_ptrs[cur_level] = cur_ptr;
+            prev_indexes[cur_level] = cur_index;
+
+            // Safety: cur_ptr will never be null and always valid.
+            let cur = unsafe { &*cur_ptr };
+            let next_ptr = cur.links[cur_level];
+            if next_ptr.is_null() {
+                if cur_level == 0 {
+                    break;
                 }
                 cur_level -= 1;
                 continue;
             }
 
-            // Safety: cur_ptr will never be null and always valid.
-            let cur_len = unsafe { (*cur_ptr).links_len[cur_level] };
+            let cur_len = cur.links_len[cur_level];
             if cur_index + cur_len < left {
                 cur_ptr = next_ptr;
+                self.metrics.visit(cur_level);
                 cur_index += cur_len;
                 continue;
             }
@@ -363,19 +1610,29 @@ impl<V> SkipList<V> {
         // Safety: prev_ptrs[i] is copy from cur_ptr above, will never be null
         // and always valid.
         let prev_node = unsafe { &mut *prev_ptrs[0] };
-        let mut next_node = prev_node.next.take();
-        for _ in left..right {
-            next_node = next_node.and_then(|mut node| node.next.take());
+        let mut first = prev_node.next.take();
+        let mut last: *mut Node<V> = &mut **first.as_mut().expect("range is not empty");
+        for _ in left + 1..right {
+            // Safety: there are right - left nodes after prev_node
+            last = &mut **unsafe { &mut *last }
+                .next
+                .as_mut()
+                .expect("range is in bounds");
         }
 
-        prev_node.next = next_node;
+        // Safety: last is a valid node in the unlinked chain
+        prev_node.next = unsafe { &mut *last }.next.take();
         match prev_node.next.as_mut() {
             None => (),
             Some(next) => next.prev = prev_ptrs[0],
         }
+        if let Some(node) = first.as_mut() {
+            node.prev = std::ptr::null_mut();
+        }
 
         self.length -= right - left;
-        right - left
+        self.metrics.remove(right - left);
+        first
     }
 
     /// Returns pointer to the given index
@@ -384,30 +1641,71 @@ impl<V> SkipList<V> {
     ///
     /// Panics if the index exceeds the length of the skiplist
     ///
-    fn _get_ptr(&self, index: usize) -> *const Node<V> {
+    pub(crate) fn _get_ptr(&self, index: usize) -> *const Node<V> {
         if self.length <= index {
             panic!("Index out of bounds.");
         }
 
+        // Safety: index is checked above.
+        unsafe { self._get_ptr_unchecked(index) }
+    }
+
+    /// Returns pointer to the given index without checking it
+    ///
+    /// # Safety
+    ///
+    /// index must be less than the length of the skiplist
+    unsafe fn _get_ptr_unchecked(&self, index: usize) -> *const Node<V> {
+        self.metrics.search();
         let actual_index = index + 1;
-        let mut cur_level = self.head.links.len() - 1;
+        let levels = self.head.links.len();
+        let mut cur_level = levels - 1;
         let mut cur_ptr: *const _ = &*self.head;
         let mut cur_index = 0;
 
+        // The finger is only a hint, it's skipped if another thread is using it.
+        let mut finger = self.finger.as_ref().and_then(|f| f.try_lock().ok());
+        let start_level = finger.as_ref().and_then(|f| f.start(actual_index, levels));
+        if let Some(f) = finger.as_mut() {
+            if start_level.is_some() {
+                let (ptr, index) = f.path[cur_level];
+                cur_ptr = ptr;
+                cur_index = index;
+            }
+            f.path.resize(levels, (std::ptr::null_mut(), 0));
+        }
+
         // Safety: cur_ptr will never be null and always valid.
         unsafe {
             while actual_index != cur_index {
-                let next_index = cur_index + (*cur_ptr).links_len[cur_level];
+                let next_index = cur_index + (&(*cur_ptr).links_len)[cur_level];
                 // cur_index != next_index means there is no next node in current level
                 if next_index <= actual_index && cur_index != next_index {
-                    cur_ptr = (*cur_ptr).links[cur_level];
+                    cur_ptr = (&(*cur_ptr).links)[cur_level];
+                    self.metrics.visit(cur_level);
                     cur_index = next_index;
                     continue;
                 }
+                if let Some(f) = finger.as_mut() {
+                    f.path[cur_level] = (cur_ptr as *mut _, cur_index);
+                }
                 cur_level -= 1;
+                if matches!(start_level, Some(start) if cur_level >= start) {
+                    if let Some(f) = finger.as_ref() {
+                        let (ptr, index) = f.path[cur_level];
+                        cur_ptr = ptr;
+                        cur_index = index;
+                    }
+                }
             }
         };
 
+        if let Some(f) = finger.as_mut() {
+            for level in 0..=cur_level {
+                f.path[level] = (cur_ptr as *mut _, cur_index);
+            }
+        }
+
         cur_ptr
     }
 
@@ -454,6 +1752,7 @@ impl<V> SkipList<V> {
         if self.length <= index {
             return None;
         }
+        self._bump_version();
 
         // Safety: index will always be valid and _get_ptr will return a valid pointer.
         let the_node = unsafe { &mut *(self._get_ptr(index) as *mut Node<V>) };
@@ -465,6 +1764,281 @@ impl<V> SkipList<V> {
         )
     }
 
+    /// Returns value at the given index without bounds checking.
+    ///
+    /// It only saves a comparison and the `Option`, the search is the same
+    /// O(log n) walk as [`get`](#method.get), so the difference is hard to see
+    /// outside of tight loops over indexes that are already known to be valid
+    /// (see the `skiplist get_unchecked` benchmark).
+    ///
+    /// # Safety
+    ///
+    /// index must be less than the length of the skiplist, otherwise the
+    /// behavior is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// let sum: i32 = (0..sk.len()).map(|i| unsafe { *sk.get_unchecked(i) }).sum();
+    /// assert_eq!(sum, 1);
+    /// ```
+    pub unsafe fn get_unchecked(&self, index: usize) -> &V {
+        let node = &*self._get_ptr_unchecked(index);
+        match node.value.as_ref() {
+            Some(value) => value,
+            None => std::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Returns mutable value at the given index without bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// index must be less than the length of the skiplist, otherwise the
+    /// behavior is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// unsafe { *sk.get_unchecked_mut(1) = 10 };
+    /// assert_eq!(sk.get(1), Some(&10));
+    /// ```
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut V {
+        self._bump_version();
+        let node = &mut *(self._get_ptr_unchecked(index) as *mut Node<V>);
+        match node.value.as_mut() {
+            Some(value) => value,
+            None => std::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Replace the value at the index, returns the old value. The node is kept,
+    /// so it's cheaper than remove and insert.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// assert_eq!(sk.replace(1, 10), 1);
+    /// assert_eq!(sk.get(1), Some(&10));
+    /// ```
+    pub fn replace(&mut self, index: usize, value: V) -> V {
+        let ptr = self._get_ptr(index) as *mut Node<V>;
+        self._bump_version();
+        // Safety: _get_ptr panics if index is out of bounds, or returns a valid pointer.
+        let the_node = unsafe { &mut *ptr };
+        the_node
+            .replace(value)
+            .expect("normal node always has a value")
+    }
+
+    /// Set the value at the index, the old value is dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.set(0, 10);
+    /// assert_eq!(sk.get(0), Some(&10));
+    /// ```
+    pub fn set(&mut self, index: usize, value: V) {
+        self.replace(index, value);
+    }
+
+    /// Overwrite values in the range with values of the iterator in one walk,
+    /// nodes are kept and old values are dropped. It stops when either the
+    /// range or the iterator runs out, returns number of overwritten values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// assert_eq!(sk.replace_range_values(2..5, vec![20, 30, 40, 50]), 3);
+    /// assert_eq!(sk.replace_range_values(8.., vec![80]), 1);
+    /// let values: Vec<_> = sk.iter().cloned().collect();
+    /// assert_eq!(values, vec![0, 1, 20, 30, 40, 5, 6, 7, 80, 9]);
+    /// ```
+    pub fn replace_range_values<R, I>(&mut self, range: R, iter: I) -> usize
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = V>,
+    {
+        let mut count = 0;
+        for (slot, value) in self.range_mut(range).zip(iter) {
+            *slot = value;
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns values at the given indexes, `None` for indexes out of bounds.
+    ///
+    /// Indexes are sorted and all values are retrieved in one left-to-right
+    /// traversal, each search resumes from the previous one instead of the head.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i * 10);
+    /// }
+    /// assert_eq!(sk.get_many(&[7, 2, 12, 2]), vec![Some(&70), Some(&20), None, Some(&20)]);
+    /// ```
+    pub fn get_many(&self, indexes: &[usize]) -> Vec<Option<&V>> {
+        let mut order: Vec<usize> = (0..indexes.len()).collect();
+        order.sort_by_key(|&i| indexes[i]);
+
+        let mut result = vec![None; indexes.len()];
+        let head: *const Node<V> = &*self.head;
+        // the last visited node and its index in every level
+        let mut path = vec![(head, 0); self.head.links.len()];
+        for i in order {
+            if indexes[i] >= self.length {
+                break;
+            }
+
+            let actual_index = indexes[i] + 1;
+            self.metrics.search();
+            let mut cur_ptr = head;
+            let mut cur_index = 0;
+            for level in (0..path.len()).rev() {
+                if path[level].1 > cur_index {
+                    cur_ptr = path[level].0;
+                    cur_index = path[level].1;
+                }
+                loop {
+                    // Safety: cur_ptr will never be null and always valid.
+                    let cur = unsafe { &*cur_ptr };
+                    let next_ptr = cur.links[level];
+                    if next_ptr.is_null() || cur_index + cur.links_len[level] > actual_index {
+                        break;
+                    }
+                    cur_index += cur.links_len[level];
+                    cur_ptr = next_ptr;
+                    self.metrics.visit(level);
+                }
+                path[level] = (cur_ptr, cur_index);
+            }
+
+            // Safety: cur_ptr is the node at actual_index
+            result[i] = unsafe { &*cur_ptr }.value.as_ref();
+        }
+        result
+    }
+
+    /// Search a skiplist sorted by the key extracted by `f`, returns `Ok` with the
+    /// index of the first matching element, or `Err` with the index where an
+    /// element of the key could be inserted keeping the order.
+    ///
+    /// The result is meaningless if the skiplist is not sorted by the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back((1, "a"));
+    /// sk.push_back((3, "b"));
+    /// sk.push_back((3, "c"));
+    ///
+    /// assert_eq!(sk.binary_search_by_key(&3, |&(k, _)| k), Ok(1));
+    /// assert_eq!(sk.binary_search_by_key(&2, |&(k, _)| k), Err(1));
+    /// ```
+    pub fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&V) -> B,
+        B: Ord,
+    {
+        let (prev_ptr, index) = self._seek_by(|v| f(v).cmp(b), false);
+        // Safety: prev_ptr will never be null and always valid.
+        match unsafe { &*prev_ptr }.next.as_deref() {
+            Some(next) if f(next.value.as_ref().expect("normal node always has a value")) == *b => {
+                Ok(index)
+            }
+            _ => Err(index),
+        }
+    }
+
+    /// Returns an iterator of indexes of the elements matching the predicate in
+    /// ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let indexes: Vec<_> = sk.find_indices(|v| v % 3 == 0).collect();
+    /// assert_eq!(indexes, vec![0, 3, 6, 9]);
+    ///
+    /// // remove from the back, so the other indexes stay valid
+    /// for index in indexes.into_iter().rev() {
+    ///     sk.remove(index);
+    /// }
+    /// assert_eq!(sk.count_where(|v| v % 3 == 0), 0);
+    /// ```
+    pub fn find_indices<F>(&self, pred: F) -> FindIndices<'_, V, F>
+    where
+        F: FnMut(&V) -> bool,
+    {
+        FindIndices {
+            iter: self.iter(),
+            index: 0,
+            pred,
+        }
+    }
+
+    /// Returns number of elements matching the predicate
+    pub fn count_where<F>(&self, mut pred: F) -> usize
+    where
+        F: FnMut(&V) -> bool,
+    {
+        self.iter().filter(|v| pred(v)).count()
+    }
+
     /// Push a value at the front of skiplist
     ///
     /// # Examples
@@ -551,10 +2125,629 @@ impl<V> SkipList<V> {
         if self.length == 0 {
             return None;
         }
-        self.get(self.length - 1)
+        self.get(self.length - 1)
+    }
+
+    /// Get the first mutable value of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// match sk.front_mut() {
+    ///     Some(v) => *v = 10,
+    ///     None => ()
+    /// };
+    /// assert_eq!(sk.front(), Some(&10));
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut V> {
+        self._bump_version();
+        self.head.next.as_mut().and_then(|node| node.value.as_mut())
+    }
+
+    /// Get the last mutable value of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// match sk.back_mut() {
+    ///     Some(v) => *v = 10,
+    ///     None => ()
+    /// };
+    /// assert_eq!(sk.back(), Some(&10));
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut V> {
+        if self.length == 0 {
+            return None;
+        }
+        self.get_mut(self.length - 1)
+    }
+
+    /// Remove the element at the end of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// assert_eq!(sk.pop_back(), Some(1));
+    /// assert_eq!(sk.pop_back(), Some(0));
+    /// assert_eq!(sk.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<V> {
+        if self.length == 0 {
+            return None;
+        }
+
+        Some(self.remove(self.length - 1))
+    }
+
+    /// Returns an iterator of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// sk.push_back(2);
+    ///
+    /// let mut i = 0;
+    /// for value in sk.iter() {
+    ///     assert_eq!(value, &i);
+    ///     i += 1;
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            current: self.head.next.as_ref().map(|node| &**node),
+            left: self.length,
+        }
+    }
+
+    /// Returns an reverse iterator of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_front(0);
+    /// sk.push_front(1);
+    /// sk.push_front(2);
+    ///
+    /// let mut i = 0;
+    /// for value in sk.reverse_iter() {
+    ///     assert_eq!(value, &i);
+    ///     i += 1;
+    /// }
+    /// ```
+    pub fn reverse_iter(&self) -> ReverseIter<'_, V> {
+        if self.length == 0 {
+            return ReverseIter {
+                current: std::ptr::null(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        ReverseIter {
+            current: self._get_ptr(self.length - 1),
+            left: self.length,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a mutable iterator of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// sk.push_back(2);
+    ///
+    /// for value in sk.iter_mut() {
+    ///     *value *= 2;
+    /// }
+    ///
+    /// let mut i = 0;
+    /// for value in sk.iter() {
+    ///     assert_eq!(value, &i);
+    ///     i += 2;
+    /// }
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        self._bump_version();
+        IterMut {
+            current: self.head.next.as_mut().map(|node| &mut **node),
+            left: self.length,
+        }
+    }
+
+    /// Returns a mutable reverse iterator of the skiplist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// sk.push_back(2);
+    ///
+    /// let mut i = 0;
+    /// for value in sk.reverse_iter_mut() {
+    ///     *value += i;
+    ///     i += 1;
+    /// }
+    ///
+    /// for value in sk.iter() {
+    ///     assert_eq!(value, &2);
+    /// }
+    /// ```
+    pub fn reverse_iter_mut(&mut self) -> ReverseIterMut<'_, V> {
+        self._bump_version();
+        if self.length == 0 {
+            return ReverseIterMut {
+                current: std::ptr::null_mut(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        ReverseIterMut {
+            current: self._get_ptr(self.length - 1) as *mut Node<V>,
+            left: self.length,
+            phantom: PhantomData,
+        }
+    }
+
+    fn _normalize_range<R>(&self, range: R) -> (usize, usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        match self._try_normalize_range(range, RangePolicy::Clamp) {
+            Ok(range) => range,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    fn _try_normalize_range<R>(
+        &self,
+        range: R,
+        policy: RangePolicy,
+    ) -> Result<(usize, usize), &'static str>
+    where
+        R: RangeBounds<usize>,
+    {
+        let left = match range.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(i) => *i,
+            Bound::Excluded(i) => *i + 1,
+        };
+
+        let mut right = match range.end_bound() {
+            Bound::Unbounded => self.length,
+            Bound::Included(i) => *i + 1,
+            Bound::Excluded(i) => *i,
+        };
+
+        if right > self.length {
+            if policy == RangePolicy::Strict {
+                return Err("Range end out of bounds.");
+            }
+            right = self.length;
+        }
+
+        if left > right {
+            return Err("Invalid range.");
+        }
+
+        Ok((left, right))
+    }
+
+    /// Returns a range iterator of the skiplist, or an error instead of a panic
+    /// if the range is invalid. Whether an end past the length is an error is
+    /// decided by the policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::{RangePolicy, SkipList};
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let values: Vec<_> = sk.range_with_policy(3..10, RangePolicy::Clamp).unwrap().collect();
+    /// assert_eq!(values, vec![&3, &4]);
+    /// assert!(sk.range_with_policy(3..10, RangePolicy::Strict).is_err());
+    /// assert!(sk.range_with_policy(3..=4, RangePolicy::Strict).is_ok());
+    /// #[allow(clippy::reversed_empty_ranges)]
+    /// let inverted = sk.range_with_policy(4..3, RangePolicy::Clamp);
+    /// assert!(inverted.is_err());
+    /// ```
+    pub fn range_with_policy<R>(
+        &self,
+        range: R,
+        policy: RangePolicy,
+    ) -> Result<Range<'_, V>, &'static str>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (left, right) = self._try_normalize_range(range, policy)?;
+        Ok(self.range(left..right))
+    }
+
+    /// Returns a range iterator of the skiplist
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let mut idx = 2;
+    /// for value in sk.range(2..7) {
+    ///     assert_eq!(value, &idx);
+    ///     idx += 1;
+    /// }
+    /// assert_eq!(idx, 7);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Range<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        if self.length == 0 {
+            return Range {
+                current: None,
+                left: 0,
+            };
+        }
+
+        let (left, right) = self._normalize_range(range);
+        if left == right {
+            return Range {
+                current: None,
+                left: 0,
+            };
+        }
+
+        // Safety: left is a valid index and _get_ptr will return a valid pointer.
+        let first = unsafe { &*self._get_ptr(left) };
+        Range {
+            current: Some(first),
+            left: right - left,
+        }
+    }
+
+    /// Returns an iterator of the first `n` elements, or all elements if there
+    /// are fewer than `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
+    /// }
+    /// assert_eq!(sk.first_n(2).cloned().collect::<Vec<_>>(), vec![0, 1]);
+    /// assert_eq!(sk.first_n(10).count(), 5);
+    /// ```
+    pub fn first_n(&self, n: usize) -> Range<'_, V> {
+        self.range(..n.min(self.length))
+    }
+
+    /// Returns an iterator of the last `n` elements, or all elements if there
+    /// are fewer than `n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
+    /// }
+    /// assert_eq!(sk.last_n(2).cloned().collect::<Vec<_>>(), vec![3, 4]);
+    /// assert_eq!(sk.last_n(10).count(), 5);
+    /// ```
+    pub fn last_n(&self, n: usize) -> Range<'_, V> {
+        self.range(self.length.saturating_sub(n)..)
+    }
+
+    /// Returns a view of the range, which can be iterated, counted and indexed
+    /// relative to the range many times without normalizing the range again
+    ///
+    /// # Panics
+    ///
+    /// Panics if range is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let window = sk.view(3..7);
+    /// assert_eq!(window.len(), 4);
+    /// assert_eq!(window.get(1), Some(&4));
+    /// assert_eq!(window.last(), Some(&6));
+    /// assert_eq!(window.iter().sum::<i32>(), 18);
+    /// assert_eq!(window.reverse_iter().next(), Some(&6));
+    /// ```
+    pub fn view<R>(&self, range: R) -> View<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (left, right) = self._normalize_range(range);
+        View {
+            sk: self,
+            left,
+            right,
+        }
+    }
+
+    /// Returns a reverse range of the skiplist
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let mut idx = 7;
+    /// for value in sk.reverse_range(..7) {
+    ///     idx -= 1;
+    ///     assert_eq!(value, &idx);
+    /// }
+    /// ```
+    pub fn reverse_range<R>(&self, range: R) -> ReverseRange<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        if self.length == 0 {
+            return ReverseRange {
+                current: std::ptr::null(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        let (left, right) = self._normalize_range(range);
+        if left == right {
+            return ReverseRange {
+                current: std::ptr::null(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        // now right is surely greater than 0
+        let last = self._get_ptr(right - 1);
+        ReverseRange {
+            current: last,
+            left: right - left,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a range iterator of the skiplist, in which elements is mutable
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    ///
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// for value in sk.range_mut(..) {
+    ///     *value *= 2;
+    /// }
+    ///
+    /// for value in sk.range(1..7) {
+    ///     assert_eq!(*value % 2, 0);
+    /// }
+    /// ```
+    pub fn range_mut<R>(&mut self, range: R) -> RangeMut<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        if self.length == 0 {
+            return RangeMut {
+                current: None,
+                left: 0,
+            };
+        }
+
+        let (left, right) = self._normalize_range(range);
+        self._bump_version();
+        if left == right {
+            return RangeMut {
+                current: None,
+                left: 0,
+            };
+        }
+
+        // Safety: left is a valid index and _get_ptr will return a valid pointer.
+        let first = unsafe { &mut *(self._get_ptr(left) as *mut _) };
+        RangeMut {
+            current: Some(first),
+            left: right - left,
+        }
+    }
+
+    /// Returns a reverse range of the skiplist
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// for i in 0..10 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let mut a = 0;
+    /// for value in sk.reverse_range_mut(..8) {
+    ///     *value += a;
+    ///     a += 1;
+    /// }
+    ///
+    /// for value in sk.range(..8) {
+    ///     assert_eq!(value, &7);
+    /// }
+    /// ```
+    pub fn reverse_range_mut<R>(&mut self, range: R) -> ReverseRangeMut<'_, V>
+    where
+        R: RangeBounds<usize>,
+    {
+        if self.length == 0 {
+            return ReverseRangeMut {
+                current: std::ptr::null_mut(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        let (left, right) = self._normalize_range(range);
+        self._bump_version();
+        if left == right {
+            return ReverseRangeMut {
+                current: std::ptr::null_mut(),
+                left: 0,
+                phantom: PhantomData,
+            };
+        }
+
+        // now right is surely greater than 0
+        let last = self._get_ptr(right - 1) as *mut _;
+        ReverseRangeMut {
+            current: last,
+            left: right - left,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Remove consecutive duplicated items
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    ///
+    /// sk.push_back(0);
+    /// sk.push_back(0);
+    /// sk.push_back(1);
+    /// sk.push_back(1);
+    /// sk.push_back(1);
+    /// sk.push_back(2);
+    ///
+    /// sk.dedup();
+    ///
+    /// let mut idx = 0;
+    /// for value in sk.iter() {
+    ///     assert_eq!(value, &idx);
+    ///     idx += 1;
+    /// }
+    /// ```
+    pub fn dedup(&mut self)
+    where
+        V: Ord,
+    {
+        if self.length == 0 {
+            return;
+        }
+
+        trace_span!("dedup", len = self.length);
+        let mut index = 0;
+        let node = self
+            .head
+            .next
+            .as_ref()
+            .expect("length is greater than 0, head won't be none");
+        let mut cur_ptr = &**node as *const Node<V>;
+
+        while !cur_ptr.is_null() {
+            // Safety: cur_ptr will not be null
+            unsafe {
+                match (*cur_ptr).next.as_ref() {
+                    None => cur_ptr = std::ptr::null(),
+                    Some(next) => match next.value.cmp(&(*cur_ptr).value) {
+                        std::cmp::Ordering::Equal => {
+                            self.remove(index + 1);
+                        }
+                        _ => {
+                            cur_ptr = &**next as *const Node<V>;
+                            index += 1;
+                        }
+                    },
+                }
+            }
+        }
     }
 
-    /// Get the first mutable value of the skiplist
+    /// Group values by the key extracted from them, values in a group keep
+    /// their order in the skiplist
     ///
     /// # Examples
     ///
@@ -562,19 +2755,43 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// match sk.front_mut() {
-    ///     Some(v) => *v = 10,
-    ///     None => ()
-    /// };
-    /// assert_eq!(sk.front(), Some(&10));
+    /// for word in "apple bob avocado cat bean".split(' ') {
+    ///     sk.push_back(word);
+    /// }
+    ///
+    /// let groups = sk.group_by_key(|word| word.chars().next());
+    /// assert_eq!(groups.get(&Some('a')), Some(&vec!["apple", "avocado"]));
+    /// assert_eq!(groups.get(&Some('b')), Some(&vec!["bob", "bean"]));
+    /// assert_eq!(groups.len(), 3);
     /// ```
-    pub fn front_mut(&mut self) -> Option<&mut V> {
-        self.head.next.as_mut().and_then(|node| node.value.as_mut())
+    pub fn group_by_key<K, F>(self, mut f: F) -> SkipMap<K, Vec<V>>
+    where
+        K: Ord,
+        F: FnMut(&V) -> K,
+    {
+        let mut groups = SkipMap::new();
+        Self::_group_into(&mut groups, self.into_iter().map(|v| (f(&v), v)));
+        groups
     }
 
-    /// Get the last mutable value of the skiplist
+    pub(crate) fn _group_into<K, I>(groups: &mut SkipMap<K, Vec<V>>, iter: I)
+    where
+        K: Ord,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            match groups.get_mut(&key) {
+                Some(group) => group.push(value),
+                None => {
+                    groups.insert(key, vec![value]);
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive repeated values like [`dedup`](#method.dedup), returns
+    /// how many times each value occurs. Counts of a value in separated runs are
+    /// added up.
     ///
     /// # Examples
     ///
@@ -582,22 +2799,61 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// match sk.back_mut() {
-    ///     Some(v) => *v = 10,
-    ///     None => ()
-    /// };
-    /// assert_eq!(sk.back(), Some(&10));
+    /// for i in [1, 1, 2, 1, 1, 1].iter() {
+    ///     sk.push_back(*i);
+    /// }
+    ///
+    /// let counts = sk.dedup_counted();
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 1]);
+    /// assert_eq!(counts.get(&1), Some(&5));
+    /// assert_eq!(counts.get(&2), Some(&1));
     /// ```
-    pub fn back_mut(&mut self) -> Option<&mut V> {
+    pub fn dedup_counted(&mut self) -> SkipMap<V, usize>
+    where
+        V: Ord + Clone,
+    {
+        let mut counts = SkipMap::new();
         if self.length == 0 {
-            return None;
+            return counts;
         }
-        self.get_mut(self.length - 1)
+
+        trace_span!("dedup_counted", len = self.length);
+        let mut index = 0;
+        let mut run = 1;
+        let node = self
+            .head
+            .next
+            .as_deref()
+            .expect("length is greater than 0, head won't be none");
+        let mut cur_ptr = node as *const Node<V>;
+
+        while !cur_ptr.is_null() {
+            // Safety: cur_ptr will not be null
+            let cur = unsafe { &*cur_ptr };
+            match cur.next.as_deref() {
+                Some(next) if next.value == cur.value => {
+                    self.remove(index + 1);
+                    run += 1;
+                }
+                next => {
+                    let value = cur.value.clone().expect("there must be value in a normal node");
+                    counts.insert_or_merge(value, run, |count, run| *count += run);
+                    run = 1;
+                    cur_ptr = next.map_or(std::ptr::null(), |next| next as *const Node<V>);
+                    index += 1;
+                }
+            }
+        }
+        counts
     }
 
-    /// Remove the element at the end of the skiplist
+    /// Split the skiplist into elements before the index and elements from the
+    /// index, like [`split_off`](#method.split_off) nodes keep their levels
+    /// and are not moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
     ///
     /// # Examples
     ///
@@ -605,21 +2861,26 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// assert_eq!(sk.pop_back(), Some(1));
-    /// assert_eq!(sk.pop_back(), Some(0));
-    /// assert_eq!(sk.pop_back(), None);
+    /// for i in 0..6 {
+    ///     sk.push_back(i);
+    /// }
+    ///
+    /// let (left, right) = sk.split_at(4);
+    /// assert_eq!(left.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// assert_eq!(right.iter().cloned().collect::<Vec<_>>(), vec![4, 5]);
     /// ```
-    pub fn pop_back(&mut self) -> Option<V> {
-        if self.length == 0 {
-            return None;
-        }
-
-        Some(self.remove(self.length - 1))
+    pub fn split_at(mut self, index: usize) -> (SkipList<V>, SkipList<V>) {
+        let right = self.split_off(index);
+        (self, right)
     }
 
-    /// Returns an iterator of the skiplist
+    /// Split the skiplist into two at the index, returns elements from the index.
+    ///
+    /// Links over the index are cut level by level, elements are not moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index exceeds the length of the skiplist
     ///
     /// # Examples
     ///
@@ -627,23 +2888,72 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// sk.push_back(2);
-    ///
-    /// let mut i = 0;
-    /// for value in sk.iter() {
-    ///     assert_eq!(value, &i);
-    ///     i += 1;
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
     /// }
+    ///
+    /// let tail = sk.split_off(3);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert_eq!(tail.iter().cloned().collect::<Vec<_>>(), vec![3, 4]);
     /// ```
-    pub fn iter(&self) -> Iter<'_, V> {
-        Iter {
-            current: self.head.next.as_ref().map(|node| &**node),
+    pub fn split_off(&mut self, index: usize) -> SkipList<V> {
+        if index > self.length {
+            panic!("Index out of bounds.");
+        }
+        self._bump_version();
+
+        trace_span!("split_off", len = self.length, index);
+        self._forget_finger();
+        let total_level = self.head.links.len();
+        let mut other = SkipList::with_level_generator(self.level_generator.clone());
+        for _ in 0..total_level {
+            other.head.increase_level();
+        }
+
+        // cut links from the last node not after the index in every level
+        let actual_index = index + 1;
+        let mut cur_ptr: *mut Node<V> = &mut *self.head;
+        let mut cur_index = 0;
+        for level in (0..total_level).rev() {
+            loop {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() || cur_index + cur.links_len[level] >= actual_index {
+                    break;
+                }
+                cur_index += cur.links_len[level];
+                cur_ptr = next_ptr;
+            }
+
+            // Safety: cur_ptr will never be null and always valid.
+            let cur = unsafe { &mut *cur_ptr };
+            if !cur.links[level].is_null() {
+                // head of other is at the index of the cut point
+                other.head.links[level] = cur.links[level];
+                other.head.links_len[level] = cur_index + cur.links_len[level] - index;
+                cur.links[level] = std::ptr::null_mut();
+                cur.links_len[level] = 0;
+            }
+        }
+
+        // Safety: cur_ptr is the node right before the index, it's never null.
+        let cur = unsafe { &mut *cur_ptr };
+        other.head.next = cur.next.take();
+        let other_head: *mut _ = &mut *other.head;
+        if let Some(first) = other.head.next.as_mut() {
+            first.prev = other_head;
         }
+
+        other.length = self.length - index;
+        self.length = index;
+        other
     }
 
-    /// Returns an reverse iterator of the skiplist
+    /// Move all elements of other to the back of the skiplist, leaving other empty.
+    ///
+    /// The first nodes of other are linked to the last nodes of the skiplist level
+    /// by level, elements are not moved.
     ///
     /// # Examples
     ///
@@ -651,31 +2961,72 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_front(0);
-    /// sk.push_front(1);
-    /// sk.push_front(2);
-    ///
-    /// let mut i = 0;
-    /// for value in sk.reverse_iter() {
-    ///     assert_eq!(value, &i);
-    ///     i += 1;
+    /// let mut other = SkipList::new();
+    /// for i in 0..3 {
+    ///     sk.push_back(i);
+    ///     other.push_back(i + 3);
     /// }
+    ///
+    /// sk.append(&mut other);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    /// assert_eq!(other.len(), 0);
     /// ```
-    pub fn reverse_iter(&self) -> ReverseIter<'_, V> {
-        if self.length == 0 {
-            return ReverseIter {
-                current: std::ptr::null(),
-                phantom: PhantomData,
-            };
+    pub fn append(&mut self, other: &mut SkipList<V>) {
+        if other.length == 0 {
+            return;
         }
+        self._bump_version();
+        other._bump_version();
 
-        ReverseIter {
-            current: self._get_ptr(self.length - 1),
-            phantom: PhantomData,
+        trace_span!("append", len = self.length, other_len = other.length);
+        self._forget_finger();
+        other._forget_finger();
+        while self.head.links.len() < other.head.links.len() {
+            self.head.increase_level();
+        }
+
+        // link the last node to the first node of other in every level
+        let mut cur_ptr: *mut Node<V> = &mut *self.head;
+        let mut cur_index = 0;
+        for level in (0..self.head.links.len()).rev() {
+            loop {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level];
+                if next_ptr.is_null() {
+                    break;
+                }
+                cur_index += cur.links_len[level];
+                cur_ptr = next_ptr;
+            }
+
+            if level < other.head.links.len() && !other.head.links[level].is_null() {
+                // Safety: cur_ptr will never be null and always valid.
+                let cur = unsafe { &mut *cur_ptr };
+                cur.links[level] = other.head.links[level];
+                cur.links_len[level] = self.length + other.head.links_len[level] - cur_index;
+                other.head.links[level] = std::ptr::null_mut();
+                other.head.links_len[level] = 0;
+            }
         }
+
+        // Safety: cur_ptr is the last node, it's never null.
+        let last = unsafe { &mut *cur_ptr };
+        last.next = other.head.next.take();
+        if let Some(first) = last.next.as_mut() {
+            first.prev = cur_ptr;
+        }
+
+        self.length += other.length;
+        other.length = 0;
     }
 
-    /// Returns a mutable iterator of the skiplist
+    /// Move the first `n` elements to the back, it's a split and two appends,
+    /// elements are not moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the length of the skiplist
     ///
     /// # Examples
     ///
@@ -683,27 +3034,32 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// sk.push_back(2);
-    ///
-    /// for value in sk.iter_mut() {
-    ///     *value *= 2;
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
     /// }
     ///
-    /// let mut i = 0;
-    /// for value in sk.iter() {
-    ///     assert_eq!(value, &i);
-    ///     i += 2;
-    /// }
+    /// sk.rotate_left(2);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![2, 3, 4, 0, 1]);
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
-        IterMut {
-            current: self.head.next.as_mut().map(|node| &mut **node),
+    pub fn rotate_left(&mut self, n: usize) {
+        if n > self.length {
+            panic!("Index out of bounds.");
+        }
+        if n == 0 || n == self.length {
+            return;
         }
+
+        let mut rest = self.split_off(n);
+        rest.append(self);
+        self.append(&mut rest);
     }
 
-    /// Returns a mutable reverse iterator of the skiplist
+    /// Move the last `n` elements to the front, see
+    /// [`rotate_left`](#method.rotate_left)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the length of the skiplist
     ///
     /// # Examples
     ///
@@ -711,66 +3067,25 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// sk.push_back(0);
-    /// sk.push_back(1);
-    /// sk.push_back(2);
-    ///
-    /// let mut i = 0;
-    /// for value in sk.reverse_iter_mut() {
-    ///     *value += i;
-    ///     i += 1;
+    /// for i in 0..5 {
+    ///     sk.push_back(i);
     /// }
     ///
-    /// for value in sk.iter() {
-    ///     assert_eq!(value, &2);
-    /// }
+    /// sk.rotate_right(2);
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![3, 4, 0, 1, 2]);
     /// ```
-    pub fn reverse_iter_mut(&mut self) -> ReverseIterMut<'_, V> {
-        if self.length == 0 {
-            return ReverseIterMut {
-                current: std::ptr::null_mut(),
-                phantom: PhantomData,
-            };
-        }
-
-        ReverseIterMut {
-            current: self._get_ptr(self.length - 1) as *mut Node<V>,
-            phantom: PhantomData,
-        }
-    }
-
-    fn _normalize_range<R>(&self, range: R) -> (usize, usize)
-    where
-        R: RangeBounds<usize>,
-    {
-        let left = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(i) => *i,
-            Bound::Excluded(i) => *i + 1,
-        };
-
-        let mut right = match range.end_bound() {
-            Bound::Unbounded => self.length,
-            Bound::Included(i) => *i + 1,
-            Bound::Excluded(i) => *i,
-        };
-
-        if right > self.length {
-            right = self.length;
-        }
-
-        if left > right {
-            panic!("Invalid range.")
+    pub fn rotate_right(&mut self, n: usize) {
+        if n > self.length {
+            panic!("Index out of bounds.");
         }
-
-        (left, right)
+        self.rotate_left(self.length - n);
     }
 
-    /// Returns a range iterator of the skiplist
+    /// Reverse the order of elements in place.
     ///
-    /// # Panics
-    ///
-    /// Panics if start_bound is greater than end_bound
+    /// Nodes are relinked in the reverse order and towers are rebuilt bottom-up
+    /// with the level generator of the skiplist, it's O(n) and values are not
+    /// moved.
     ///
     /// # Examples
     ///
@@ -778,49 +3093,41 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// for i in 0..10 {
+    /// for i in 0..5 {
     ///     sk.push_back(i);
     /// }
     ///
-    /// let mut idx = 2;
-    /// for value in sk.range(2..7) {
-    ///     assert_eq!(value, &idx);
-    ///     idx += 1;
-    /// }
-    /// assert_eq!(idx, 7);
+    /// sk.reverse();
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+    /// assert_eq!(sk.get(1), Some(&3));
     /// ```
-    pub fn range<R>(&self, range: R) -> Range<'_, V>
-    where
-        R: RangeBounds<usize>,
-    {
-        if self.length == 0 {
-            return Range {
-                current: None,
-                left: 0,
-            };
-        }
-
-        let (left, right) = self._normalize_range(range);
-        if left == right {
-            return Range {
-                current: None,
-                left: 0,
-            };
+    pub fn reverse(&mut self) {
+        let head_ptr: *mut Node<V> = &mut *self.head;
+        let mut rest = self.head.next.take();
+        let mut reversed: Option<Box<Node<V>>> = None;
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+            let node_ptr: *mut Node<V> = &mut *node;
+            if let Some(first) = reversed.as_mut() {
+                first.prev = node_ptr;
+            }
+            node.next = reversed;
+            reversed = Some(node);
         }
-
-        // Safety: left is a valid index and _get_ptr will return a valid pointer.
-        let first = unsafe { &*self._get_ptr(left) };
-        Range {
-            current: Some(first),
-            left: right - left,
+        if let Some(first) = reversed.as_mut() {
+            first.prev = head_ptr;
         }
+        self.head.next = reversed;
+        self.rebuild();
     }
 
-    /// Returns a reverse range of the skiplist
+    /// Split the skiplist into `n` parts by index, sizes of the parts differ by
+    /// at most 1 and the leading parts are the longer ones. Parts can be processed
+    /// separately and joined again by [`append`](#method.append).
     ///
     /// # Panics
     ///
-    /// Panics if start_bound is greater than end_bound
+    /// Panics if `n` is 0
     ///
     /// # Examples
     ///
@@ -828,51 +3135,39 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    /// for i in 0..10 {
+    /// for i in 0..7 {
     ///     sk.push_back(i);
     /// }
     ///
-    /// let mut idx = 7;
-    /// for value in sk.reverse_range(..7) {
-    ///     idx -= 1;
-    ///     assert_eq!(value, &idx);
+    /// let mut parts = sk.split_into(3);
+    /// let lens: Vec<_> = parts.iter().map(|part| part.len()).collect();
+    /// assert_eq!(lens, vec![3, 2, 2]);
+    ///
+    /// let mut sk = SkipList::new();
+    /// for part in parts.iter_mut() {
+    ///     sk.append(part);
     /// }
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
     /// ```
-    pub fn reverse_range<R>(&self, range: R) -> ReverseRange<'_, V>
-    where
-        R: RangeBounds<usize>,
-    {
-        if self.length == 0 {
-            return ReverseRange {
-                current: std::ptr::null(),
-                left: 0,
-                phantom: PhantomData,
-            };
-        }
-
-        let (left, right) = self._normalize_range(range);
-        if left == right {
-            return ReverseRange {
-                current: std::ptr::null(),
-                left: 0,
-                phantom: PhantomData,
-            };
-        }
-
-        // now right is surely greater than 0
-        let last = self._get_ptr(right - 1);
-        ReverseRange {
-            current: last,
-            left: right - left,
-            phantom: PhantomData,
-        }
+    pub fn split_into(mut self, n: usize) -> Vec<SkipList<V>> {
+        assert!(n > 0, "number of parts should be greater than 0");
+        let starts: Vec<_> = (1..n).map(|i| self._part_start(n, i)).collect();
+        let mut parts: Vec<_> = starts
+            .into_iter()
+            .rev()
+            .map(|start| self.split_off(start))
+            .collect();
+        parts.push(self);
+        parts.reverse();
+        parts
     }
 
-    /// Returns a range iterator of the skiplist, in which elements is mutable
+    /// Returns `n` range iterators that split the skiplist by index the same way
+    /// as [`split_into`](#method.split_into) without changing the skiplist.
     ///
     /// # Panics
     ///
-    /// Panics if start_bound is greater than end_bound
+    /// Panics if `n` is 0
     ///
     /// # Examples
     ///
@@ -880,47 +3175,28 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    ///
-    /// for i in 0..10 {
+    /// for i in 0..7 {
     ///     sk.push_back(i);
     /// }
     ///
-    /// for value in sk.range_mut(..) {
-    ///     *value *= 2;
-    /// }
-    ///
-    /// for value in sk.range(1..7) {
-    ///     assert_eq!(*value % 2, 0);
-    /// }
+    /// let sums: Vec<i32> = sk.chunks_exact_views(3).into_iter().map(|view| view.sum()).collect();
+    /// assert_eq!(sums, vec![3, 7, 11]);
     /// ```
-    pub fn range_mut<R>(&mut self, range: R) -> RangeMut<'_, V>
-    where
-        R: RangeBounds<usize>,
-    {
-        if self.length == 0 {
-            return RangeMut {
-                current: None,
-                left: 0,
-            };
-        }
-
-        let (left, right) = self._normalize_range(range);
-        if left == right {
-            return RangeMut {
-                current: None,
-                left: 0,
-            };
-        }
+    pub fn chunks_exact_views(&self, n: usize) -> Vec<Range<'_, V>> {
+        assert!(n > 0, "number of parts should be greater than 0");
+        (0..n)
+            .map(|i| self.range(self._part_start(n, i)..self._part_start(n, i + 1)))
+            .collect()
+    }
 
-        // Safety: left is a valid index and _get_ptr will return a valid pointer.
-        let first = unsafe { &mut *(self._get_ptr(left) as *mut _) };
-        RangeMut {
-            current: Some(first),
-            left: right - left,
-        }
+    // index of the first element of the i-th part when split into n parts
+    fn _part_start(&self, n: usize, i: usize) -> usize {
+        let (size, rest) = (self.length / n, self.length % n);
+        i * size + i.min(rest)
     }
 
-    /// Returns a reverse range of the skiplist
+    /// Returns a skiplist with clones of values in the index range, the values
+    /// are cloned in one traversal and linked in one splice.
     ///
     /// # Panics
     ///
@@ -936,47 +3212,21 @@ impl<V> SkipList<V> {
     ///     sk.push_back(i);
     /// }
     ///
-    /// let mut a = 0;
-    /// for value in sk.reverse_range_mut(..8) {
-    ///     *value += a;
-    ///     a += 1;
-    /// }
-    ///
-    /// for value in sk.range(..8) {
-    ///     assert_eq!(value, &7);
-    /// }
+    /// let part = sk.clone_range(3..6);
+    /// assert_eq!(part.iter().cloned().collect::<Vec<_>>(), vec![3, 4, 5]);
     /// ```
-    pub fn reverse_range_mut<R>(&mut self, range: R) -> ReverseRangeMut<'_, V>
+    pub fn clone_range<R>(&self, range: R) -> SkipList<V>
     where
+        V: Clone,
         R: RangeBounds<usize>,
     {
-        if self.length == 0 {
-            return ReverseRangeMut {
-                current: std::ptr::null_mut(),
-                left: 0,
-                phantom: PhantomData,
-            };
-        }
-
-        let (left, right) = self._normalize_range(range);
-        if left == right {
-            return ReverseRangeMut {
-                current: std::ptr::null_mut(),
-                left: 0,
-                phantom: PhantomData,
-            };
-        }
-
-        // now right is surely greater than 0
-        let last = self._get_ptr(right - 1) as *mut _;
-        ReverseRangeMut {
-            current: last,
-            left: right - left,
-            phantom: PhantomData,
-        }
+        let mut result = SkipList::with_level_generator(self.level_generator.clone());
+        result._insert_iter_at(0, self.range(range).cloned());
+        result
     }
 
-    /// Remove consecutive duplicated items
+    /// Returns a skiplist with values of the skiplist repeated `n` times, the
+    /// cloned values are linked in one splice.
     ///
     /// # Examples
     ///
@@ -984,55 +3234,19 @@ impl<V> SkipList<V> {
     /// use skiplist::skiplist::SkipList;
     ///
     /// let mut sk = SkipList::new();
-    ///
-    /// sk.push_back(0);
     /// sk.push_back(0);
     /// sk.push_back(1);
-    /// sk.push_back(1);
-    /// sk.push_back(1);
-    /// sk.push_back(2);
-    ///
-    /// sk.dedup();
     ///
-    /// let mut idx = 0;
-    /// for value in sk.iter() {
-    ///     assert_eq!(value, &idx);
-    ///     idx += 1;
-    /// }
+    /// let repeated = sk.repeat(3);
+    /// assert_eq!(repeated.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 0, 1, 0, 1]);
     /// ```
-    pub fn dedup(&mut self)
+    pub fn repeat(&self, n: usize) -> SkipList<V>
     where
-        V: Ord,
+        V: Clone,
     {
-        if self.length == 0 {
-            return;
-        }
-
-        let mut index = 0;
-        let node = self
-            .head
-            .next
-            .as_ref()
-            .expect("length is greater than 0, head won't be none");
-        let mut cur_ptr = &**node as *const Node<V>;
-
-        while !cur_ptr.is_null() {
-            // Safety: cur_ptr will not be null
-            unsafe {
-                match (*cur_ptr).next.as_ref() {
-                    None => cur_ptr = std::ptr::null(),
-                    Some(next) => match next.value.cmp(&(*cur_ptr).value) {
-                        std::cmp::Ordering::Equal => {
-                            self.remove(index + 1);
-                        }
-                        _ => {
-                            cur_ptr = &**next as *const Node<V>;
-                            index += 1;
-                        }
-                    },
-                }
-            }
-        }
+        let mut result = SkipList::with_level_generator(self.level_generator.clone());
+        result._insert_iter_at(0, (0..n).flat_map(|_| self.iter().cloned()));
+        result
     }
 
     /// Returns the length of the skiplist
@@ -1059,111 +3273,260 @@ impl<V> SkipList<V> {
         V: std::fmt::Display,
         R: RangeBounds<usize>,
     {
-        const ELEMENT_EMPTY_PART1_1: &str = "-----";
-        const ELEMENT_EMPTY_PART1_2: &str = "------";
-        const ELEMENT_PART2_1: &str = "--> ";
-        const ELEMENT_PART2_2: &str = "----";
+        let mut result = String::new();
+        self.explain_to(range, &mut result)?;
+        Ok(result)
+    }
+
+    /// Write the graph of [`explain`](#method.explain) to the writer, without
+    /// building it in a `String` first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back("a");
+    /// let mut graph = String::new();
+    /// sk.explain_to(.., &mut graph).unwrap();
+    /// assert_eq!(Ok(graph), sk.explain(..));
+    /// ```
+    pub fn explain_to<R, W>(&self, range: R, w: &mut W) -> Result<(), &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+        W: std::fmt::Write,
+    {
         const MAX_SPAN: usize = 20;
 
         let (left, right) = self._normalize_range(range);
-        let span = right - left;
-        if span > MAX_SPAN {
+        if right - left > MAX_SPAN {
             return Err("Range span is too big, the span should be smaller than 20");
         }
+        self._write_graph(left, right, w)
+            .map_err(|_| "Failed to write the graph")
+    }
+
+    fn _write_graph<W>(&self, left: usize, right: usize, w: &mut W) -> std::fmt::Result
+    where
+        V: std::fmt::Display,
+        W: std::fmt::Write,
+    {
+        const ELEMENT_EMPTY_PART1_1: &str = "-----";
+        const ELEMENT_EMPTY_PART1_2: &str = "------";
+        const ELEMENT_PART2_1: &str = "--> ";
+        const ELEMENT_PART2_2: &str = "----";
 
+        let span = right - left;
         let levels = self.head.links.len();
-        let mut result = format!(
+        write!(
+            w,
             "start: {}, levels: {}, show_len: {}, total_len: {}",
             left,
             levels,
-            right - left,
+            span,
             self.len()
-        );
-        let mut l_lines = vec![String::from(""); levels];
-        if span > 0 {
+        )?;
+
+        // every level is a line, lines are written from the top level and each
+        // of them walks the nodes in the range again
+        for level in (0..levels).rev() {
+            w.write_char('\n')?;
+            if span == 0 {
+                continue;
+            }
             // Safety: left is a valid index, _get_ptr will return a valid pointer
             let mut cur = unsafe { &*self._get_ptr(left) };
             for idx in 0..span {
-                let next = cur.next.as_ref();
-                for level in 0..levels {
-                    if cur.links.len() > level {
-                        l_lines[level].push_str(&format!("[+{}] ", idx));
-                    } else {
-                        if idx < 10 {
-                            l_lines[level].push_str(ELEMENT_EMPTY_PART1_1);
+                if cur.links.len() > level {
+                    write!(w, "[+{}] ", idx)?;
+                } else if idx < 10 {
+                    w.write_str(ELEMENT_EMPTY_PART1_1)?;
+                } else {
+                    w.write_str(ELEMENT_EMPTY_PART1_2)?;
+                }
+                match cur.next.as_deref() {
+                    None => w.write_str(ELEMENT_PART2_1)?,
+                    Some(next) => {
+                        if next.links.len() > level {
+                            w.write_str(ELEMENT_PART2_1)?;
                         } else {
-                            l_lines[level].push_str(ELEMENT_EMPTY_PART1_2);
-                        }
-                    }
-                    match next {
-                        None => l_lines[level].push_str(ELEMENT_PART2_1),
-                        Some(node) => {
-                            if node.links.len() > level {
-                                l_lines[level].push_str(ELEMENT_PART2_1);
-                            } else {
-                                l_lines[level].push_str(ELEMENT_PART2_2);
-                            }
+                            w.write_str(ELEMENT_PART2_2)?;
                         }
+                        cur = next;
                     }
                 }
-                match next {
-                    None => (),
-                    Some(next) => cur = &**next,
-                }
             }
         }
 
-        for level in (0..levels).rev() {
-            result.push_str("\n");
-            result.push_str(&l_lines[level]);
-        }
-
-        result.push_str("\nvalues:\n");
+        w.write_str("\nvalues:\n")?;
 
         if span > 0 {
             // Safety: left is a valid index, _get_ptr will return a valid pointer
             let mut cur = unsafe { &*self._get_ptr(left) };
             for idx in 0..span {
-                result.push_str(&format!(
+                writeln!(
+                    w,
                     "[+{}]: {}",
                     idx,
                     cur.value.as_ref().expect("normal node always has a value")
-                ));
-                result.push_str("\n");
-                match cur.next.as_ref() {
+                )?;
+                match cur.next.as_deref() {
                     None => (),
-                    Some(next) => cur = &**next,
+                    Some(next) => cur = next,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl<V> SkipList<V> {
+    /// Check links, lengths of links and prev pointers of every node, panic if
+    /// anything is broken
+    pub(crate) fn _check_integrity(&self) {
+        // index of every node in level 0, head is 0
+        let mut indexes = std::collections::HashMap::new();
+        let mut prev_ptr: *const Node<V> = &*self.head;
+        indexes.insert(prev_ptr, 0);
+        let mut cur = self.head.next.as_deref();
+        while let Some(node) = cur {
+            assert_eq!(node.prev as *const _, prev_ptr, "broken prev pointer");
+            assert!(node.value.is_some(), "normal node without value");
+            assert!(node.links.len() <= self.head.links.len(), "node is higher than head");
+            prev_ptr = node;
+            indexes.insert(prev_ptr, indexes.len());
+            cur = node.next.as_deref();
+        }
+        assert_eq!(indexes.len(), self.length + 1, "wrong length");
+
+        for level in 0..self.head.links.len() {
+            let mut cur_ptr: *const Node<V> = &*self.head;
+            loop {
+                // Safety: nodes in links are all valid if the skiplist is not broken
+                let cur = unsafe { &*cur_ptr };
+                let next_ptr = cur.links[level] as *const Node<V>;
+                if next_ptr.is_null() {
+                    assert_eq!(cur.links_len[level], 0, "link to null should be 0");
+                    break;
                 }
+                let (cur_index, next_index) = (indexes[&cur_ptr], indexes[&next_ptr]);
+                assert_eq!(cur_index + cur.links_len[level], next_index, "wrong link length");
+                cur_ptr = next_ptr;
+            }
+        }
+    }
+}
+
+impl<V: std::fmt::Debug> std::fmt::Debug for SkipList<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<V: std::fmt::Display> std::fmt::Display for SkipList<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "{}", value)?;
         }
+        write!(f, "]")
+    }
+}
+
+impl<V: Clone> Clone for SkipList<V> {
+    /// Clone values into a skiplist built in one splice, its towers are chosen
+    /// by the same level generator instead of n random-level inserts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.extend(0..5);
+    ///
+    /// let mut cloned = sk.clone();
+    /// cloned.push_back(5);
+    /// assert_eq!(sk.len(), 5);
+    /// assert_eq!(cloned.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    /// ```
+    fn clone(&self) -> Self {
+        let mut result = SkipList::with_level_generator(self.level_generator.clone());
+        result.set_finger(self.has_finger());
+        result._insert_iter_at(0, self.iter().cloned());
+        result
+    }
+}
+
+impl<V: PartialEq> PartialEq for SkipList<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Eq> Eq for SkipList<V> {}
+
+impl<V: PartialOrd> PartialOrd for SkipList<V> {
+    /// Compare skiplists lexicographically like `Vec`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut a = SkipList::new();
+    /// a.extend(vec![1, 2, 3]);
+    /// let mut b = SkipList::new();
+    /// b.extend(vec![1, 3]);
+    /// assert!(a < b);
+    /// ```
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
 
-        Ok(result)
+impl<V: Ord> Ord for SkipList<V> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.iter().cmp(other.iter())
     }
 }
 
-impl<V: std::fmt::Debug> std::fmt::Debug for SkipList<V> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "[")?;
-        for (i, value) in self.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ")?;
-            }
-            write!(f, "{:?}", value)?;
-        }
-        write!(f, "]")
+impl<V> Extend<V> for SkipList<V> {
+    /// Push values of the iterator at the back in one splice
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        self._insert_iter_at(self.length, iter);
     }
 }
 
-impl<V: std::fmt::Display> std::fmt::Display for SkipList<V> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "[")?;
-        for (i, value) in self.iter().enumerate() {
-            if i != 0 {
-                write!(f, ", ")?;
-            }
-            write!(f, "{}", value)?;
-        }
-        write!(f, "]")
+impl<'a, V: Copy + 'a> Extend<&'a V> for SkipList<V> {
+    /// Push copies of values of the iterator at the back in one splice
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut sk = SkipList::new();
+    /// sk.push_back(0);
+    /// sk.extend([1, 2].iter());
+    /// assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// ```
+    fn extend<I: IntoIterator<Item = &'a V>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
     }
 }
 
@@ -1187,19 +3550,132 @@ impl<V> IntoIterator for SkipList<V> {
     ///     assert_eq!(value, idx);
     ///     idx += 1;
     /// }
+    ///
+    /// // values can be moved out from the back
+    /// let mut sk = SkipList::new();
+    /// sk.extend_front(0..3);
+    /// let values: Vec<_> = sk.into_iter().rev().collect();
+    /// assert_eq!(values, vec![2, 1, 0]);
     /// ```
     fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
 
+impl<V> std::ops::Add for SkipList<V> {
+    type Output = SkipList<V>;
+
+    /// Concatenate two skiplists, nodes of `rhs` are linked after the last node
+    /// of `self`, see [`SkipList::append`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut a = SkipList::new();
+    /// let mut b = SkipList::new();
+    /// a.push_back(0);
+    /// b.push_back(1);
+    ///
+    /// let c = a + b;
+    /// assert_eq!(c.iter().cloned().collect::<Vec<_>>(), vec![0, 1]);
+    /// ```
+    fn add(mut self, mut rhs: SkipList<V>) -> Self::Output {
+        self.append(&mut rhs);
+        self
+    }
+}
+
+impl<V> std::ops::AddAssign for SkipList<V> {
+    /// Append nodes of `rhs` to the skiplist, see [`SkipList::append`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skiplist::SkipList;
+    ///
+    /// let mut a = SkipList::new();
+    /// let mut b = SkipList::new();
+    /// a.push_back(0);
+    /// b.push_back(1);
+    ///
+    /// a += b;
+    /// assert_eq!(a.iter().cloned().collect::<Vec<_>>(), vec![0, 1]);
+    /// ```
+    fn add_assign(&mut self, mut rhs: SkipList<V>) {
+        self.append(&mut rhs);
+    }
+}
+
+/// An iterator whose position can be saved as a token, created by
+/// [`SkipList::iter_versioned`]
+pub struct VersionedIter<'a, V> {
+    inner: Range<'a, V>,
+    version: u64,
+    index: usize,
+}
+
+/// Position of a [`VersionedIter`] and the version of its skiplist
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IterToken {
+    version: u64,
+    index: usize,
+}
+
+impl<'a, V> VersionedIter<'a, V> {
+    /// Returns the token of the next position
+    pub fn token(&self) -> IterToken {
+        IterToken {
+            version: self.version,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, V> Iterator for VersionedIter<'a, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, V> FusedIterator for VersionedIter<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for VersionedIter<'a, V> {}
+
 pub struct Iter<'a, V> {
     current: Option<&'a Node<V>>,
+    left: usize,
 }
 
 unsafe impl<'a, V: Sync> Sync for Iter<'a, V> {}
 unsafe impl<'a, V: Send> Send for Iter<'a, V> {}
 
+impl<'a, V> Clone for Iter<'a, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            current: self.current,
+            left: self.left,
+        }
+    }
+}
+
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for Iter<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&self.clone().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for Iter<'a, V> {
     type Item = &'a V;
 
@@ -1207,29 +3683,225 @@ impl<'a, V> Iterator for Iter<'a, V> {
     fn next(&mut self) -> Option<Self::Item> {
         self.current.and_then(|node| {
             self.current = node.next.as_ref().map(|node| &**node);
+            self.left -= 1;
             node.value.as_ref()
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<'a, V> FusedIterator for Iter<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for Iter<'a, V> {}
+
+/// An iterator of indexes of elements matching a predicate.
+///
+/// This `struct` is created by the [`find_indices`] method on [`SkipList`].
+///
+/// [`SkipList`]: struct.SkipList.html
+/// [`find_indices`]: struct.SkipList.html#method.find_indices
+pub struct FindIndices<'a, V, F> {
+    iter: Iter<'a, V>,
+    // index of the next element of iter
+    index: usize,
+    pred: F,
+}
+
+impl<'a, V, F> Iterator for FindIndices<'a, V, F>
+where
+    F: FnMut(&V) -> bool,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in &mut self.iter {
+            let index = self.index;
+            self.index += 1;
+            if (self.pred)(value) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.iter.len()))
+    }
+}
+
+impl<'a, V, F> FusedIterator for FindIndices<'a, V, F>
+where
+    F: FnMut(&V) -> bool,
+{
 }
 
 pub struct IntoIter<V>(SkipList<V>);
 
+impl<V: std::fmt::Debug> std::fmt::Debug for IntoIter<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.0.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<V> Iterator for IntoIter<V> {
     type Item = V;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<V> DoubleEndedIterator for IntoIter<V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<V> FusedIterator for IntoIter<V> {}
+
+impl<V> ExactSizeIterator for IntoIter<V> {}
+
+/// Values removed by [`SkipList::extract_if`]
+pub struct ExtractIf<'a, V, F>
+where
+    F: FnMut(&mut V) -> bool,
+{
+    sk: &'a mut SkipList<V>,
+    // the last kept node in every level, head if there is none
+    kept: Vec<*mut Node<V>>,
+    pred: F,
+}
+
+impl<'a, V, F> Iterator for ExtractIf<'a, V, F>
+where
+    F: FnMut(&mut V) -> bool,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let prev_ptr = *self.kept.first()?;
+            // Safety: kept nodes will never be null and always valid.
+            let prev = unsafe { &mut *prev_ptr };
+            let node_ptr: *mut Node<V> = &mut **prev.next.as_mut()?;
+            self.sk.metrics.visit(0);
+
+            // Safety: node_ptr is owned by prev and valid.
+            let node = unsafe { &mut *node_ptr };
+            let value = node.value.as_mut().expect("normal node always has a value");
+            if !(self.pred)(value) {
+                for kept in self.kept.iter_mut().take(node.links.len()) {
+                    *kept = node_ptr;
+                }
+                continue;
+            }
+
+            // Nodes after the kept ones are all after the node, so links from
+            // them either point to the node or over it.
+            for (level, &kept_ptr) in self.kept.iter().enumerate() {
+                // Safety: kept nodes will never be null and always valid.
+                let kept = unsafe { &mut *kept_ptr };
+                if kept.links[level] == node_ptr {
+                    kept.links[level] = node.links[level];
+                    kept.links_len[level] = if node.links[level].is_null() {
+                        0
+                    } else {
+                        kept.links_len[level] + node.links_len[level] - 1
+                    };
+                } else if !kept.links[level].is_null() {
+                    kept.links_len[level] -= 1;
+                }
+            }
+
+            let mut the_node = prev.next.take().expect("the node is checked above");
+            prev.next = the_node.next.take().map(|mut next| {
+                next.prev = prev_ptr;
+                next
+            });
+            self.sk.length -= 1;
+            self.sk.metrics.remove(1);
+            return Some(self.sk._recycle_node(the_node));
+        }
+    }
+}
+
+impl<'a, V, F> FusedIterator for ExtractIf<'a, V, F>
+where
+    F: FnMut(&mut V) -> bool,
+{
+}
+
+/// Owned values removed by [`SkipList::drain`], [`SkipList::drain_range`] or
+/// [`SkipList::splice`]
+pub struct Drain<V> {
+    first: Option<Box<Node<V>>>,
+    len: usize,
+}
+
+impl<V> Iterator for Drain<V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node = self.first.take()?;
+        self.first = node.next.take();
+        self.len -= 1;
+        node.value.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<V> FusedIterator for Drain<V> {}
+
+impl<V> ExactSizeIterator for Drain<V> {}
+
+impl<V> Drop for Drain<V> {
+    fn drop(&mut self) {
+        // Drop nodes one by one, otherwise dropping the chain is recursive.
+        while let Some(mut node) = self.first.take() {
+            self.first = node.next.take();
+        }
+    }
 }
 
 pub struct ReverseIter<'a, V> {
     current: *const Node<V>,
+    left: usize,
     phantom: PhantomData<&'a V>,
 }
 
 unsafe impl<'a, V: Sync> Sync for ReverseIter<'a, V> {}
 unsafe impl<'a, V: Send> Send for ReverseIter<'a, V> {}
 
+impl<'a, V> Clone for ReverseIter<'a, V> {
+    fn clone(&self) -> Self {
+        ReverseIter {
+            current: self.current,
+            left: self.left,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for ReverseIter<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("ReverseIter")
+            .field(&self.clone().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for ReverseIter<'a, V> {
     type Item = &'a V;
 
@@ -1238,6 +3910,8 @@ impl<'a, V> Iterator for ReverseIter<'a, V> {
             return None;
         }
 
+        self.left -= 1;
+
         // Safety: `current` won't be null when the program run to here
         // `current` is a normal node, every normal node has a prev_node
         unsafe {
@@ -1251,31 +3925,75 @@ impl<'a, V> Iterator for ReverseIter<'a, V> {
             result
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
 }
 
+impl<'a, V> FusedIterator for ReverseIter<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for ReverseIter<'a, V> {}
+
 pub struct IterMut<'a, V> {
     current: Option<&'a mut Node<V>>,
+    left: usize,
 }
 
 unsafe impl<'a, V: Sync> Sync for IterMut<'a, V> {}
 unsafe impl<'a, V: Send> Send for IterMut<'a, V> {}
 
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for IterMut<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let remaining = Iter {
+            current: self.current.as_deref(),
+            left: self.left,
+        };
+        f.debug_tuple("IterMut")
+            .field(&remaining.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for IterMut<'a, V> {
     type Item = &'a mut V;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.current.take().map(|node| {
             self.current = node.next.as_mut().map(|node| &mut **node);
+            self.left -= 1;
             node.value.as_mut().expect("normal node always has a value")
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
 }
 
+impl<'a, V> FusedIterator for IterMut<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for IterMut<'a, V> {}
+
 pub struct ReverseIterMut<'a, V> {
     current: *mut Node<V>,
+    left: usize,
     phantom: PhantomData<&'a V>,
 }
 
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for ReverseIterMut<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let remaining: ReverseIter<'_, V> = ReverseIter {
+            current: self.current,
+            left: self.left,
+            phantom: PhantomData,
+        };
+        f.debug_tuple("ReverseIterMut")
+            .field(&remaining.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for ReverseIterMut<'a, V> {
     type Item = &'a mut V;
 
@@ -1284,6 +4002,8 @@ impl<'a, V> Iterator for ReverseIterMut<'a, V> {
             return None;
         }
 
+        self.left -= 1;
+
         // Safety: `current` won't be null when the program run to here
         // `current` is a normal node, every normal node has a prev_node
         unsafe {
@@ -1297,6 +4017,96 @@ impl<'a, V> Iterator for ReverseIterMut<'a, V> {
             result
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<'a, V> FusedIterator for ReverseIterMut<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for ReverseIterMut<'a, V> {}
+
+/// A window of a [`SkipList`] by index, it can be iterated, counted and
+/// indexed many times, every use seeks from its bounds.
+///
+/// This `struct` is created by the [`view`] method on [`SkipList`].
+///
+/// [`view`]: SkipList::view
+pub struct View<'a, V> {
+    sk: &'a SkipList<V>,
+    left: usize,
+    right: usize,
+}
+
+impl<'a, V> Clone for View<'a, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, V> Copy for View<'a, V> {}
+
+impl<'a, V> View<'a, V> {
+    /// Returns number of elements in the view
+    pub fn len(&self) -> usize {
+        self.right - self.left
+    }
+
+    /// Returns true if there is no element in the view
+    pub fn is_empty(&self) -> bool {
+        self.left == self.right
+    }
+
+    /// Returns index of the first element of the view in the skiplist
+    pub fn offset(&self) -> usize {
+        self.left
+    }
+
+    /// Returns the element at the index relative to the view
+    pub fn get(&self, index: usize) -> Option<&'a V> {
+        if index >= self.len() {
+            return None;
+        }
+        self.sk.get(self.left + index)
+    }
+
+    /// Returns the first element of the view
+    pub fn first(&self) -> Option<&'a V> {
+        self.get(0)
+    }
+
+    /// Returns the last element of the view
+    pub fn last(&self) -> Option<&'a V> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns an iterator of the view
+    pub fn iter(&self) -> Range<'a, V> {
+        self.sk.range(self.left..self.right)
+    }
+
+    /// Returns a reverse iterator of the view
+    pub fn reverse_iter(&self) -> ReverseRange<'a, V> {
+        self.sk.reverse_range(self.left..self.right)
+    }
+}
+
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for View<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("View")
+            .field(&self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<'a, V> IntoIterator for View<'a, V> {
+    type Item = &'a V;
+    type IntoIter = Range<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 pub struct Range<'a, V> {
@@ -1307,6 +4117,23 @@ pub struct Range<'a, V> {
 unsafe impl<'a, V: Sync> Sync for Range<'a, V> {}
 unsafe impl<'a, V: Send> Send for Range<'a, V> {}
 
+impl<'a, V> Clone for Range<'a, V> {
+    fn clone(&self) -> Self {
+        Range {
+            current: self.current,
+            left: self.left,
+        }
+    }
+}
+
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for Range<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("Range")
+            .field(&self.clone().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for Range<'a, V> {
     type Item = &'a V;
 
@@ -1319,8 +4146,16 @@ impl<'a, V> Iterator for Range<'a, V> {
             node.value.as_ref()
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
 }
 
+impl<'a, V> FusedIterator for Range<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for Range<'a, V> {}
+
 pub struct ReverseRange<'a, V> {
     current: *const Node<V>,
     left: usize,
@@ -1330,6 +4165,24 @@ pub struct ReverseRange<'a, V> {
 unsafe impl<'a, V: Sync> Sync for ReverseRange<'a, V> {}
 unsafe impl<'a, V: Send> Send for ReverseRange<'a, V> {}
 
+impl<'a, V> Clone for ReverseRange<'a, V> {
+    fn clone(&self) -> Self {
+        ReverseRange {
+            current: self.current,
+            left: self.left,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for ReverseRange<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("ReverseRange")
+            .field(&self.clone().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for ReverseRange<'a, V> {
     type Item = &'a V;
 
@@ -1358,13 +4211,33 @@ impl<'a, V> Iterator for ReverseRange<'a, V> {
             result
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
 }
 
+impl<'a, V> FusedIterator for ReverseRange<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for ReverseRange<'a, V> {}
+
 pub struct RangeMut<'a, V> {
     current: Option<&'a mut Node<V>>,
     left: usize,
 }
 
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for RangeMut<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let remaining = Range {
+            current: self.current.as_deref(),
+            left: self.left,
+        };
+        f.debug_tuple("RangeMut")
+            .field(&remaining.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for RangeMut<'a, V> {
     type Item = &'a mut V;
 
@@ -1377,8 +4250,16 @@ impl<'a, V> Iterator for RangeMut<'a, V> {
             node.value.as_mut()
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
 }
 
+impl<'a, V> FusedIterator for RangeMut<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for RangeMut<'a, V> {}
+
 pub struct ReverseRangeMut<'a, V> {
     current: *mut Node<V>,
     left: usize,
@@ -1388,6 +4269,19 @@ pub struct ReverseRangeMut<'a, V> {
 unsafe impl<'a, V: Sync> Sync for ReverseRangeMut<'a, V> {}
 unsafe impl<'a, V: Send> Send for ReverseRangeMut<'a, V> {}
 
+impl<'a, V: std::fmt::Debug> std::fmt::Debug for ReverseRangeMut<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let remaining: ReverseRange<'_, V> = ReverseRange {
+            current: self.current,
+            left: self.left,
+            phantom: PhantomData,
+        };
+        f.debug_tuple("ReverseRangeMut")
+            .field(&remaining.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
 impl<'a, V> Iterator for ReverseRangeMut<'a, V> {
     type Item = &'a mut V;
 
@@ -1416,6 +4310,129 @@ impl<'a, V> Iterator for ReverseRangeMut<'a, V> {
             result
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<'a, V> FusedIterator for ReverseRangeMut<'a, V> {}
+
+impl<'a, V> ExactSizeIterator for ReverseRangeMut<'a, V> {}
+
+/// A cursor over a skiplist with editing operations.
+///
+/// The cursor points to an element, or to the "ghost" position between the last
+/// and the first element. It is created by [`SkipList::cursor_mut`].
+pub struct CursorMut<'a, V> {
+    list: &'a mut SkipList<V>,
+    // head of the list when the cursor is at the ghost position
+    current: *mut Node<V>,
+    // index counting head as 0
+    index: usize,
+}
+
+impl<'a, V> CursorMut<'a, V> {
+    /// Returns index of the current element, or `None` at the ghost position
+    pub fn index(&self) -> Option<usize> {
+        self.index.checked_sub(1)
+    }
+
+    /// Returns the current element, or `None` at the ghost position
+    pub fn current(&mut self) -> Option<&mut V> {
+        self.list._bump_version();
+        // Safety: current is head or a node in the list
+        unsafe { &mut *self.current }.value.as_mut()
+    }
+
+    /// Move to the next element, moves to the ghost position after the last element
+    /// and to the first element after the ghost position.
+    pub fn move_next(&mut self) {
+        // Safety: current is head or a node in the list
+        let cur = unsafe { &mut *self.current };
+        match cur.next.as_mut() {
+            Some(next) => {
+                self.current = &mut **next;
+                self.index += 1;
+            }
+            None => {
+                self.current = &mut *self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Move to the previous element, moves to the ghost position before the first
+    /// element and to the last element before the ghost position.
+    pub fn move_prev(&mut self) {
+        if self.index > 1 {
+            // Safety: current is a normal node, it always has a prev node
+            self.current = unsafe { &*self.current }.prev;
+            self.index -= 1;
+        } else if self.index == 1 || self.list.length == 0 {
+            self.current = &mut *self.list.head;
+            self.index = 0;
+        } else {
+            self.current = self.list._get_ptr(self.list.length - 1) as *mut Node<V>;
+            self.index = self.list.length;
+        }
+    }
+
+    /// Insert value after the current element, or at the front at the ghost position.
+    /// The cursor doesn't move.
+    pub fn insert_after(&mut self, value: V) {
+        self.list._insert_after_ptr(self.current, self.index, value);
+    }
+
+    /// Insert value before the current element, or at the back at the ghost position.
+    /// The cursor doesn't move.
+    pub fn insert_before(&mut self, value: V) {
+        if self.index == 0 {
+            self.list.push_back(value);
+            return;
+        }
+        // Safety: current is a normal node, it always has a prev node
+        let prev_ptr = unsafe { &*self.current }.prev;
+        self.list._insert_after_ptr(prev_ptr, self.index - 1, value);
+        self.index += 1;
+    }
+}
+
+impl<'a, V> std::fmt::Debug for CursorMut<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.index()).finish()
+    }
+}
+
+/// An opaque handle of an element in a skiplist, returned by
+/// [`SkipList::insert_with_handle`](struct.SkipList.html#method.insert_with_handle).
+///
+/// It points to the element directly, so it's only valid while the element is in
+/// the skiplist, which is why methods taking it are unsafe.
+pub struct NodeRef<V> {
+    ptr: *mut Node<V>,
+}
+
+impl<V> Clone for NodeRef<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for NodeRef<V> {}
+
+impl<V> PartialEq for NodeRef<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<V> Eq for NodeRef<V> {}
+
+impl<V> std::fmt::Debug for NodeRef<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("NodeRef").field(&self.ptr).finish()
+    }
 }
 
 impl<V> Drop for SkipList<V> {
@@ -1579,6 +4596,659 @@ mod test {
         assert_eq!(sk.get(0), Some(&2));
     }
 
+    #[test]
+    fn insert_iter_at() {
+        let mut sk = SkipList::new();
+        let mut expected = vec![];
+        for i in 0..50 {
+            let index = (i * 7) % (expected.len() + 1);
+            let values: Vec<_> = (0..i % 5).map(|j| i * 10 + j).collect();
+            expected.splice(index..index, values.iter().cloned());
+            assert_eq!(sk._insert_iter_at(index, values), i % 5);
+        }
+
+        assert_eq!(sk.len(), expected.len());
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(value));
+        }
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), expected);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn cursor_insert() {
+        let mut sk = SkipList::new();
+        sk.push_back(0);
+        let mut expected = vec![0];
+        {
+            let mut cursor = sk.cursor_mut(0);
+            for i in 1..200 {
+                if i % 3 == 0 {
+                    cursor.move_next();
+                } else if i % 7 == 0 {
+                    cursor.move_prev();
+                }
+                let index = cursor.index().unwrap_or(expected.len());
+                if i % 2 == 0 {
+                    cursor.insert_after(i);
+                    let at = cursor.index().map_or(0, |index| index + 1);
+                    expected.insert(at, i);
+                } else {
+                    cursor.insert_before(i);
+                    expected.insert(index, i);
+                }
+            }
+        }
+
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), expected);
+        for (i, value) in expected.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(value));
+        }
+        sk._check_integrity();
+
+        // a cursor at the length starts at the ghost position
+        let mut cursor = sk.cursor_mut(expected.len());
+        assert_eq!(cursor.index(), None);
+        cursor.insert_before(1000);
+        cursor.insert_after(-1);
+        assert_eq!(sk.front(), Some(&-1));
+        assert_eq!(sk.back(), Some(&1000));
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn handle_insert() {
+        let mut sk = SkipList::new();
+        let mut expected = vec![0];
+        let mut handles = vec![sk.insert_with_handle(0, 0)];
+        for i in 1..500 {
+            let at = (i * 7919) % handles.len();
+            // Safety: no element is removed, so all handles are valid
+            let handle = unsafe {
+                if i % 2 == 0 {
+                    sk.insert_after_handle(handles[at], i)
+                } else {
+                    sk.insert_before_handle(handles[at], i)
+                }
+            };
+            let index = expected.iter().position(|v| *v == at).unwrap();
+            expected.insert(if i % 2 == 0 { index + 1 } else { index }, i);
+            handles.push(handle);
+        }
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), expected);
+        sk._check_integrity();
+    }
+
+    #[test]
+    #[cfg(feature = "metrics")]
+    fn metrics() {
+        let mut sk = SkipList::new();
+        for i in 0..100 {
+            sk.push_back(i);
+        }
+        sk.reset_metrics();
+        assert_eq!(sk.get(50), Some(&50));
+        let metrics = sk.metrics();
+        assert_eq!(metrics.searches, 1);
+        assert!(metrics.nodes_visited > 0 && metrics.nodes_visited < 100);
+        let visits = sk.level_visits();
+        assert_eq!(visits.len(), sk.head.links.len());
+        assert_eq!(visits.iter().sum::<u64>(), metrics.nodes_visited);
+
+        sk._unlink_range(1, 11);
+        assert_eq!(sk.metrics().removes, 10);
+    }
+
+    #[test]
+    fn node_handles() {
+        let mut sk = SkipList::new();
+        let handles: Vec<_> = (0..100).map(|i| sk.insert_with_handle(i, i)).collect();
+        sk.remove_range(10..20);
+        sk.extend_front(vec![1000, 1001]);
+
+        // Safety: elements of the handles are not removed
+        unsafe {
+            for i in (0..10).chain(20..100) {
+                let index = sk.index_of_handle(handles[i]);
+                assert_eq!(sk.get(index), Some(&i));
+            }
+            assert_eq!(sk.next_handle(handles[9]), Some(handles[20]));
+            assert_eq!(sk.prev_handle(handles[20]), Some(handles[9]));
+            assert_eq!(sk.next_handle(handles[99]), None);
+
+            *sk.get_mut_by_handle(handles[50]) = 500;
+            assert_eq!(sk.remove_by_handle(handles[50]), 500);
+        }
+        assert_eq!(sk.len(), 91);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn get_many() {
+        let mut sk = SkipList::new();
+        for i in 0..100 {
+            sk.push_back(i);
+        }
+
+        let indexes: Vec<_> = (0..60).map(|i| (i * 37) % 120).collect();
+        let expected: Vec<_> = indexes.iter().map(|&i| sk.get(i)).collect();
+        assert_eq!(sk.get_many(&indexes), expected);
+        assert_eq!(sk.get_many(&[]), vec![]);
+        assert_eq!(SkipList::<i32>::new().get_many(&[0]), vec![None]);
+    }
+
+    #[test]
+    fn split_and_append() {
+        for len in 0..40 {
+            let mut sk = SkipList::new();
+            for i in 0..len {
+                sk.push_back(i);
+            }
+
+            let mut parts = sk.split_into(4);
+            for part in parts.iter() {
+                part._check_integrity();
+            }
+
+            let mut joined = SkipList::new();
+            for part in parts.iter_mut() {
+                joined.append(part);
+                part._check_integrity();
+                assert_eq!(part.len(), 0);
+            }
+            joined._check_integrity();
+            assert_eq!(joined.iter().cloned().collect::<Vec<_>>(), (0..len).collect::<Vec<_>>());
+
+            joined.push_back(len);
+            joined.push_front(-1);
+            joined._check_integrity();
+            assert_eq!(joined.get(len as usize + 1), Some(&len));
+        }
+    }
+
+    #[test]
+    fn iter_clone_and_debug() {
+        let mut sk = SkipList::new();
+        for i in 0..5 {
+            sk.push_back(i);
+        }
+
+        let mut iter = sk.iter();
+        iter.next();
+        let peek = iter.clone();
+        assert_eq!(peek.collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+        assert_eq!(iter.next(), Some(&1));
+
+        assert_eq!(format!("{:?}", sk.range(1..3)), "Range([1, 2])");
+        assert_eq!(format!("{:?}", sk.reverse_iter()), "ReverseIter([4, 3, 2, 1, 0])");
+        assert_eq!(format!("{:?}", sk.reverse_range_mut(..2)), "ReverseRangeMut([1, 0])");
+        assert_eq!(format!("{:?}", sk.iter_mut()), "IterMut([0, 1, 2, 3, 4])");
+    }
+
+    #[test]
+    fn resume_iter_checks_token() {
+        let mut a = SkipList::new();
+        a.extend(0..10);
+        let mut b = SkipList::new();
+        b.extend(0..2);
+        while b.version() < a.version() {
+            b.get_mut(0);
+        }
+        assert_eq!(a.version(), b.version());
+
+        let mut it = a.iter_versioned();
+        it.by_ref().take(8).for_each(drop);
+        let token = it.token();
+        assert!(b.resume_iter(token).is_err());
+        assert_eq!(a.resume_iter(token).unwrap().count(), 2);
+
+        // appending an empty list changes nothing
+        a.append(&mut SkipList::new());
+        assert!(a.resume_iter(token).is_ok());
+        b.append(&mut a);
+        assert!(b.resume_iter(token).is_err());
+    }
+
+    #[test]
+    fn find_indices_size_hint() {
+        let mut sk = SkipList::new();
+        sk.extend(0..10);
+        let mut indexes = sk.find_indices(|v| v % 3 == 0);
+        assert_eq!(indexes.size_hint(), (0, Some(10)));
+        assert_eq!(indexes.next(), Some(0));
+        assert_eq!(indexes.next(), Some(3));
+        assert_eq!(indexes.size_hint(), (0, Some(6)));
+    }
+
+    #[test]
+    fn version_after_validation() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut sk = SkipList::new();
+        sk.extend(0..3);
+        let version = sk.version();
+        assert!(catch_unwind(AssertUnwindSafe(|| sk.insert(5, 0))).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| sk.replace(3, 0))).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| sk.split_off(4))).is_err());
+        assert_eq!(sk.get_mut(3), None);
+        assert_eq!(sk.remove_range(1..1), 0);
+        assert_eq!(sk.version(), version);
+
+        sk.get_mut(2);
+        assert_ne!(sk.version(), version);
+    }
+
+    #[test]
+    fn node_pool() {
+        let mut sk = SkipList::new();
+        sk.set_node_pool_capacity(8);
+        for i in 0..2000 {
+            sk.insert(sk.len() / 2, i);
+            if i % 3 == 2 {
+                sk.remove(sk.len() / 3);
+                sk.pop_front();
+            }
+        }
+        assert!(sk.node_pool_len() <= 8);
+        sk._check_integrity();
+
+        let mut ordered = crate::ordered_skiplist::OrderedSkipList::new();
+        ordered.sk.set_node_pool_capacity(4);
+        for i in 0..500 {
+            ordered.insert((i * 7919) % 1000);
+            if i % 2 == 1 {
+                ordered.remove(0);
+            }
+        }
+        ordered.sk._check_integrity();
+        assert!(ordered.iter().zip(ordered.iter().skip(1)).all(|(a, b)| a < b));
+    }
+
+    #[test]
+    fn node_pool_bulk() {
+        let mut sk = SkipList::new();
+        sk.set_node_pool_capacity(64);
+        sk.extend(0..100);
+        assert_eq!(sk.remove_range(10..60), 50);
+        assert_eq!(sk.node_pool_len(), 50);
+
+        // bulk inserts take nodes of the levels they pick from the pool
+        sk.splice(0..0, 0..200);
+        assert!(sk.node_pool_len() < 50);
+        sk._check_integrity();
+
+        sk.clear();
+        assert_eq!(sk.node_pool_len(), 64);
+        sk.extend(0..10);
+        assert!(sk.node_pool_len() < 64);
+        assert_eq!(sk.iter().cloned().collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+        sk._check_integrity();
+
+        // drained nodes leave with the iterator
+        let pooled = sk.node_pool_len();
+        assert_eq!(sk.drain().count(), 10);
+        assert_eq!(sk.node_pool_len(), pooled);
+    }
+
+    #[test]
+    fn fused() {
+        fn assert_fused<I: FusedIterator>(mut iter: I) {
+            while iter.next().is_some() {}
+            for _ in 0..3 {
+                assert!(iter.next().is_none());
+            }
+        }
+
+        let mut sk = SkipList::new();
+        sk.extend(0..10);
+        assert_fused(sk.iter());
+        assert_fused(sk.reverse_iter());
+        assert_fused(sk.range(2..5));
+        assert_fused(sk.reverse_range(2..5));
+        assert_fused(sk.find_indices(|v| *v % 2 == 0));
+        assert_fused(sk.range_mut(..3));
+        assert_fused(sk.reverse_range_mut(7..));
+        assert_fused(sk.iter_mut());
+        assert_fused(sk.reverse_iter_mut());
+        assert_fused(sk.extract_if(|v| *v % 3 == 0));
+        assert_fused(sk.drain_range(..2));
+        assert_fused(sk.clone().drain());
+        assert_fused(sk.into_iter());
+    }
+
+    #[test]
+    fn import_levels() {
+        let levels: Vec<usize> = (0..500).map(|i| (i % 7) / 2 + 1).collect();
+        let sk = SkipList::import_levels((0..500).zip(levels.iter().cloned())).unwrap();
+        sk._check_integrity();
+        assert_eq!(sk.len(), 500);
+        assert_eq!(sk.export_levels(), levels);
+        assert_eq!(sk.head.links.len(), 4);
+        assert!(sk.iter().cloned().eq(0..500));
+        assert!(sk.reverse_iter().cloned().eq((0..500).rev()));
+
+        let mut sk = SkipList::new();
+        sk.extend(0..300);
+        let levels = sk.export_levels();
+        let mut copy = SkipList::import_levels(sk.iter().cloned().zip(levels)).unwrap();
+        copy._check_integrity();
+        assert_eq!(copy.head.links_len, sk.head.links_len);
+        copy.insert(150, 0);
+        copy._check_integrity();
+
+        let max = SkipList::<i32>::new().max_levels();
+        let sk = SkipList::import_levels(vec![(0, 1), (1, max)]).unwrap();
+        sk._check_integrity();
+        assert_eq!(sk.head.links.len(), max);
+        assert!(SkipList::import_levels(vec![(0, 1), (1, max + 1), (2, 1)]).is_err());
+        assert!(SkipList::import_levels(vec![(0, 1), (1, usize::MAX)]).is_err());
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut sk = SkipList::new();
+        assert_eq!(sk.iter().len(), 0);
+        assert_eq!(sk.reverse_iter().len(), 0);
+        sk.extend(0..100);
+
+        let mut iter = sk.iter();
+        let mut reverse = sk.reverse_iter();
+        for left in (0..100).rev() {
+            iter.next();
+            reverse.next();
+            assert_eq!(iter.size_hint(), (left, Some(left)));
+            assert_eq!(reverse.len(), left);
+        }
+        assert_eq!(sk.range(10..30).len(), 20);
+        assert_eq!(sk.reverse_range(10..30).skip(5).len(), 15);
+        assert_eq!(sk.range_mut(..30).len(), 30);
+        assert_eq!(sk.reverse_range_mut(90..).len(), 10);
+        assert_eq!(sk.iter_mut().skip(1).len(), 99);
+        assert_eq!(sk.reverse_iter_mut().len(), 100);
+        assert_eq!(sk.iter_versioned().len(), 100);
+    }
+
+    #[test]
+    fn compare() {
+        let lists: Vec<Vec<i32>> = vec![
+            vec![],
+            vec![0],
+            vec![0, 1],
+            vec![0, 2],
+            vec![1],
+            vec![1, 0, 0],
+        ];
+        let sks: Vec<SkipList<i32>> = lists
+            .iter()
+            .map(|list| {
+                let mut sk = SkipList::new();
+                sk.extend(list.iter());
+                sk
+            })
+            .collect();
+        for (i, a) in sks.iter().enumerate() {
+            for (j, b) in sks.iter().enumerate() {
+                assert_eq!(a.cmp(b), lists[i].cmp(&lists[j]));
+                assert_eq!(a == b, i == j);
+            }
+        }
+
+        let mut sorted = sks.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert!(sorted == sks);
+    }
+
+    #[test]
+    fn view() {
+        let mut sk = SkipList::new();
+        for i in 0..100 {
+            sk.push_back(i);
+        }
+        let window = sk.view(10..20);
+        for _ in 0..2 {
+            assert!(window.iter().cloned().eq(10..20));
+            assert!(window.reverse_iter().cloned().eq((10..20).rev()));
+        }
+        for i in 0..10 {
+            assert_eq!(window.get(i), Some(&(i + 10)));
+        }
+        assert_eq!(window.get(10), None);
+        assert_eq!(window.offset(), 10);
+
+        let empty = sk.view(100..);
+        assert!(empty.is_empty());
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn clone() {
+        let mut sk = SkipList::with_config(0.25, 8);
+        sk.set_finger(true);
+        sk.extend((0..1000).map(|i| i.to_string()));
+        let mut cloned = sk.clone();
+        cloned._check_integrity();
+        assert_eq!(cloned.probability(), 0.25);
+        assert!(cloned.has_finger());
+        assert!(cloned.iter().eq(sk.iter()));
+
+        cloned.remove(0);
+        cloned.set(0, "x".to_string());
+        assert_eq!(sk.get(0).map(|s| s.as_str()), Some("0"));
+        assert_eq!(sk.get(1).map(|s| s.as_str()), Some("1"));
+        assert_eq!(cloned.len(), 999);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut sk = SkipList::with_config(0.25, 8);
+        sk.reverse();
+        assert_eq!(sk.len(), 0);
+        for i in 0..1000 {
+            sk.push_back(i);
+        }
+        sk.reverse();
+        sk._check_integrity();
+        assert_eq!(sk.probability(), 0.25);
+        assert!(sk.iter().cloned().eq((0..1000).rev()));
+        assert!(sk.reverse_iter().cloned().eq(0..1000));
+        for i in 0..1000 {
+            assert_eq!(sk.get(i), Some(&(999 - i)));
+        }
+        sk.insert(500, 1000);
+        sk.remove(0);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn rotate() {
+        let mut sk = SkipList::new();
+        let mut vec = vec![];
+        for i in 0..500 {
+            sk.push_back(i);
+            vec.push(i);
+        }
+        for &n in [0, 1, 499, 500, 250, 137].iter() {
+            sk.rotate_left(n);
+            vec.rotate_left(n);
+            sk._check_integrity();
+            assert!(sk.iter().eq(vec.iter()));
+            sk.rotate_right(n / 3);
+            vec.rotate_right(n / 3);
+            sk._check_integrity();
+            assert!(sk.iter().eq(vec.iter()));
+        }
+        for (i, v) in vec.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(v));
+        }
+    }
+
+    #[test]
+    fn splice() {
+        let mut sk = SkipList::new();
+        let mut vec = vec![];
+        for i in 0..1000 {
+            sk.push_back(i);
+            vec.push(i);
+        }
+        for (i, &(left, right, n)) in [(0, 0, 3), (10, 500, 0), (100, 300, 1000), (0, 10, 10)]
+            .iter()
+            .enumerate()
+        {
+            let new_values: Vec<_> = (0..n).map(|j| i * 10000 + j).collect();
+            let removed: Vec<_> = sk.splice(left..right, new_values.clone()).collect();
+            let expected: Vec<_> = vec.splice(left..right, new_values).collect();
+            assert_eq!(removed, expected);
+            sk._check_integrity();
+            assert!(sk.iter().eq(vec.iter()));
+        }
+        let len = sk.len();
+        assert_eq!(sk.splice(len - 5.., vec![]).len(), 5);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn clear() {
+        let mut sk = SkipList::new();
+        for i in 0..1000 {
+            sk.push_back(i);
+        }
+        sk.clear();
+        assert_eq!(sk.len(), 0);
+        assert_eq!(sk.head.links.len(), 0);
+        assert_eq!(sk.iter().next(), None);
+        sk._check_integrity();
+        for i in 0..10 {
+            sk.insert(i, i);
+        }
+        sk._check_integrity();
+        assert!(sk.iter().cloned().eq(0..10));
+    }
+
+    #[test]
+    fn extract_if() {
+        let mut sk = SkipList::new();
+        let mut vec = vec![];
+        for i in 0..2000 {
+            sk.push_back(i);
+            vec.push(i);
+        }
+
+        let removed: Vec<_> = sk.extract_if(|v| *v % 3 == 0 || *v > 1500).collect();
+        assert!(removed.iter().all(|v| *v % 3 == 0 || *v > 1500));
+        vec.retain(|v| *v % 3 != 0 && *v <= 1500);
+        sk._check_integrity();
+        assert!(sk.iter().eq(vec.iter()));
+
+        // stop early, the rest is kept
+        let mut it = sk.extract_if(|v| {
+            *v += 1;
+            *v % 2 == 0
+        });
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.next(), Some(6));
+        drop(it);
+        sk._check_integrity();
+        assert_eq!(sk.iter().take(4).cloned().collect::<Vec<_>>(), vec![3, 5, 7, 8]);
+        for (i, v) in sk.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(v));
+        }
+
+        assert_eq!(sk.extract_if(|_| true).count(), vec.len() - 2);
+        assert_eq!(sk.len(), 0);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn finger() {
+        let mut sk = SkipList::new();
+        let mut vec = vec![];
+        sk.set_finger(true);
+        let mut pos = 0;
+        for i in 0..5000usize {
+            // mostly local accesses, with jumps and splices in between
+            pos = match i % 50 {
+                0 => (i * 7919) % (vec.len() + 1),
+                _ => (pos + i % 7).saturating_sub(3).min(vec.len()),
+            };
+            match i % 5 {
+                0 | 1 => {
+                    sk.insert(pos, i);
+                    vec.insert(pos, i);
+                }
+                2 if pos < vec.len() => assert_eq!(sk.remove(pos), vec.remove(pos)),
+                3 if pos < vec.len() => {
+                    *sk.get_mut(pos).unwrap() += 1;
+                    vec[pos] += 1;
+                }
+                _ => assert_eq!(sk.get(pos), vec.get(pos)),
+            }
+            if i % 1000 == 999 {
+                sk.remove_range(10..20);
+                vec.drain(10..20);
+                sk.extend_front(vec![1, 2, 3]);
+                vec.splice(0..0, vec![1, 2, 3]);
+                sk._check_integrity();
+            }
+        }
+        sk._check_integrity();
+        assert!(sk.iter().eq(vec.iter()));
+        for (i, v) in vec.iter().enumerate() {
+            assert_eq!(sk.get(i), Some(v));
+        }
+
+        sk.set_finger(false);
+        assert!(!sk.has_finger());
+        sk.push_back(0);
+        sk._check_integrity();
+    }
+
+    #[test]
+    fn rebuild() {
+        let mut sk = SkipList::new();
+        for i in 0..1000 {
+            sk.push_back(i);
+        }
+        sk.remove_range(100..900);
+        sk.set_probability(0.25);
+        sk.rebuild();
+        sk._check_integrity();
+        assert!(sk.iter().cloned().eq((0..100).chain(900..1000)));
+        for i in 0..sk.len() {
+            assert_eq!(sk.get(i), Some(&if i < 100 { i } else { i + 800 }));
+        }
+
+        let mut empty: SkipList<i32> = SkipList::new();
+        empty.rebuild();
+        empty.push_back(1);
+        empty._check_integrity();
+    }
+
+    #[test]
+    fn drain_range() {
+        let mut sk = SkipList::new();
+        for i in 0..1000 {
+            sk.push_back(i);
+        }
+        let mut drained = sk.drain_range(100..900);
+        assert_eq!(drained.len(), 800);
+        assert!(drained.by_ref().take(10).eq(100..110));
+        assert_eq!(drained.len(), 790);
+        drop(drained);
+        sk._check_integrity();
+        assert!(sk.iter().cloned().eq((0..100).chain(900..1000)));
+
+        assert!(sk.drain_range(150..).eq(950..1000));
+        assert!(sk.drain_range(..50).eq(0..50));
+        sk._check_integrity();
+        assert!(sk.drain().eq((50..100).chain(900..950)));
+        sk._check_integrity();
+        sk.push_back(1);
+        sk._check_integrity();
+    }
+
     #[test]
     fn explain() {
         use rand;
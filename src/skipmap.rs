@@ -1,85 +1,2253 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+use std::ops::Bound;
+use std::sync::mpsc::{self, Receiver, Sender};
 
-struct Node<K: Ord, V> {
-    next: Option<Box<Node<K, V>>>,
-    nexts: Vec<*mut Node<K, V>>,
-    prev: *mut Node<K, V>,
-    key: Option<K>,
-    value: Option<V>,
+#[cfg(feature = "bloom")]
+use std::hash::Hash;
+
+#[cfg(feature = "bloom")]
+use crate::bloom::{self, BloomFilter};
+use crate::keyed_skiplist::KeyedSkipList;
+use crate::level_generator::LevelGenerator;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::ordered_skiplist::SeekPath;
+use crate::skiplist;
+use crate::skiplist::Node;
+use crate::skipset::SkipSet;
+use crate::value_range::ValueRange;
+
+pub(crate) struct Bucket<K, V> {
+    pub(crate) key: K,
+    pub(crate) value: V,
+    // neighbors in the order list, only maintained if order tracking is enabled
+    older: *mut Node<Bucket<K, V>>,
+    newer: *mut Node<Bucket<K, V>>,
+}
+
+impl<K, V> Bucket<K, V> {
+    pub(crate) fn new(key: K, value: V) -> Self {
+        Self {
+            key,
+            value,
+            older: std::ptr::null_mut(),
+            newer: std::ptr::null_mut(),
+        }
+    }
 }
 
-struct SkipMap<K: Ord, V> {
-    head: Box<Node<K, V>>,
-    tail: *mut Node<K, V>,
-    length: usize,
+impl<K: std::fmt::Display, V: std::fmt::Display> std::fmt::Display for Bucket<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
 }
 
-struct Iter<'a, K, V> {}
+/// Which order the entries of a map are threaded on besides the key order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderTracking {
+    /// Entries are ordered by the time they are inserted, replacing the value
+    /// of an existing key doesn't change its position
+    Insertion,
+    /// Entries are ordered by the time they are inserted or last accessed by
+    /// `insert`, `insert_or_merge`, `get_mut`, `first_key_value_mut`,
+    /// `last_key_value_mut` or `touch`. Iterating with `iter_mut` or
+    /// `range_mut` doesn't move entries.
+    Access,
+}
+
+/// A change of an entry in a watched key range, see [`SkipMap::watch_range`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event<K, V> {
+    /// A new key is inserted with the value
+    Insert(K, V),
+    /// The value of an existing key is replaced or merged, carries the new value
+    Update(K, V),
+    /// The key is removed, carries the removed value
+    Remove(K, V),
+}
 
-struct IterMut<'a, K, V> {}
+struct Watcher<K, V> {
+    start: Bound<K>,
+    end: Bound<K>,
+    sender: Sender<Event<K, V>>,
+    // watch_range knows K and V are Clone, the rest of the map doesn't
+    clone_key: fn(&K) -> K,
+    clone_value: fn(&V) -> V,
+}
 
-struct IntoIter<K, V> {}
+impl<K: Ord, V> Watcher<K, V> {
+    fn contains(&self, key: &K) -> bool {
+        let after_start = match &self.start {
+            Bound::Included(start) => key >= start,
+            Bound::Excluded(start) => key > start,
+            Bound::Unbounded => true,
+        };
+        let before_end = match &self.end {
+            Bound::Included(end) => key <= end,
+            Bound::Excluded(end) => key < end,
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
+    }
+}
 
-struct Range<'a, K, V> {}
+pub struct SkipMap<K: Ord, V> {
+    pub(crate) sk: KeyedSkipList<K, Bucket<K, V>>,
+    order_tracking: Option<OrderTracking>,
+    oldest: *mut Node<Bucket<K, V>>,
+    newest: *mut Node<Bucket<K, V>>,
+    #[cfg(feature = "bloom")]
+    filter: Option<BloomFilter<K>>,
+    watchers: Vec<Watcher<K, V>>,
+}
 
-struct RangeMut<'a, K, V> {}
+unsafe impl<K: Ord + Sync, V: Sync> Sync for SkipMap<K, V> {}
+unsafe impl<K: Ord + Send, V: Send> Send for SkipMap<K, V> {}
 
 impl<K: Ord, V> SkipMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::new())
+    }
+
+    pub fn with_level_generator(lg: LevelGenerator) -> Self {
+        SkipMap {
+            sk: KeyedSkipList::with_config(false, lg, |bucket| &bucket.key),
+            order_tracking: None,
+            oldest: std::ptr::null_mut(),
+            newest: std::ptr::null_mut(),
+            #[cfg(feature = "bloom")]
+            filter: None,
+            watchers: vec![],
+        }
+    }
+
+    /// Create a map from entries sorted by key in ascending order without
+    /// duplicated keys, the entries are linked in one splice without being
+    /// compared.
+    ///
+    /// The order is only verified in debug builds, the map is broken if the keys
+    /// are not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let sm = SkipMap::from_sorted_iter_unchecked(vec![(1, "a"), (2, "b")]);
+    /// assert_eq!(sm.get(&2), Some(&"b"));
+    /// ```
+    pub fn from_sorted_iter_unchecked<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut sm = Self::new();
+        sm.sk
+            .sk
+            .extend_front(iter.into_iter().map(|(k, v)| Bucket::new(k, v)));
+        debug_assert!(
+            sm.keys().zip(sm.keys().skip(1)).all(|(a, b)| a < b),
+            "keys should be sorted without duplicates"
+        );
+        sm
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Choose the levels of all elements again, see
+    /// [`SkipList::rebuild`](../skiplist/struct.SkipList.html#method.rebuild)
+    pub fn rebuild(&mut self) {
+        self.sk.rebuild()
+    }
+
+    /// Returns numbers of operations since the map is created or its
+    /// metrics are reset
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.sk.metrics()
+    }
+
+    /// Reset all metrics to 0
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&self) {
+        self.sk.reset_metrics()
+    }
+
+    /// Create a map which additionally threads its entries on a list in
+    /// insertion (or access) order, see [`SkipMap::iter_insertion_order`]
+    /// and [`SkipMap::pop_oldest`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::{OrderTracking, SkipMap};
+    ///
+    /// let mut sm = SkipMap::with_order_tracking(OrderTracking::Insertion);
+    /// sm.insert(3, "c");
+    /// sm.insert(1, "a");
+    /// sm.insert(2, "b");
+    ///
+    /// let keys: Vec<_> = sm.iter_insertion_order().map(|(k, _)| *k).collect();
+    /// assert_eq!(keys, vec![3, 1, 2]);
+    /// assert_eq!(sm.pop_oldest(), Some((3, "c")));
+    /// ```
+    pub fn with_order_tracking(tracking: OrderTracking) -> Self {
+        let mut sm = Self::new();
+        sm.order_tracking = Some(tracking);
+        sm
+    }
+
+    /// Create a map that maintains a bloom filter of its keys, so absent keys can
+    /// be answered by [`SkipMap::get_filtered`] and [`SkipMap::contains_key_filtered`]
+    /// without searching the map.
+    ///
+    /// The filter is sized for about `expected_items` keys, it has more false
+    /// positives if the map grows beyond that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::with_bloom_filter(100, 0.01);
+    /// sm.insert("a", 1);
+    /// sm.insert("b", 2);
+    /// sm.remove(&"b");
+    /// assert_eq!(sm.get_filtered(&"a"), Some(&1));
+    /// assert!(!sm.contains_key_filtered(&"b"));
+    /// ```
+    #[cfg(feature = "bloom")]
+    pub fn with_bloom_filter(expected_items: usize, false_positive_rate: f64) -> Self
+    where
+        K: Hash,
+    {
+        let mut sm = Self::new();
+        sm.filter = Some(BloomFilter::new(expected_items, false_positive_rate));
+        sm
+    }
+
+    /// Returns the order tracking mode of the map
+    pub fn order_tracking(&self) -> Option<OrderTracking> {
+        self.order_tracking
+    }
+
+    /// Insert a key-value pair, returns the old value if the key exists.
+    /// The key in the map is not updated in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// assert_eq!(sm.insert(1, "a"), None);
+    /// assert_eq!(sm.insert(1, "b"), Some("a"));
+    /// assert_eq!(sm.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(node_ptr) = self._find_ptr(&key) {
+            self._touch_ptr(node_ptr);
+            // Safety: _find_ptr returns a valid normal node
+            let bucket = unsafe { &mut *node_ptr }
+                .value
+                .as_mut()
+                .expect("normal node always has a value");
+            let old = std::mem::replace(&mut bucket.value, value);
+            self._notify(&bucket.key, &bucket.value, Event::Update);
+            return Some(old);
+        }
+
+        self._insert_new(key, value);
+        None
+    }
+
+    /// Insert a key-value pair, if the key exists the new value is merged into the
+    /// old one by `merge` instead of replacing it. Returns the value in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut counter = SkipMap::new();
+    /// for word in "a b a c a".split(' ') {
+    ///     counter.insert_or_merge(word, 1, |count, new| *count += new);
+    /// }
+    /// assert_eq!(counter.get(&"a"), Some(&3));
+    ///
+    /// let mut groups = SkipMap::new();
+    /// groups.insert_or_merge(1, vec!["a"], |old, mut new| old.append(&mut new));
+    /// groups.insert_or_merge(1, vec!["b"], |old, mut new| old.append(&mut new));
+    /// assert_eq!(groups.get(&1), Some(&vec!["a", "b"]));
+    /// ```
+    pub fn insert_or_merge<F>(&mut self, key: K, value: V, merge: F) -> &mut V
+    where
+        F: FnOnce(&mut V, V),
+    {
+        let node_ptr = match self._find_ptr(&key) {
+            Some(node_ptr) => {
+                self._touch_ptr(node_ptr);
+                // Safety: _find_ptr returns a valid normal node
+                let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+                merge(&mut bucket.value, value);
+                self._notify(&bucket.key, &bucket.value, Event::Update);
+                node_ptr
+            }
+            None => self._insert_new(key, value),
+        };
+        // Safety: node_ptr is a normal node of the map, the returned reference
+        // borrows self mutably
+        &mut unsafe { Self::_bucket_mut(node_ptr) }.value
+    }
+
+    /// Get the value of the key
+    pub fn get<Q: ?Sized>(&self, q: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.get_first(q).map(|(_, bucket)| &bucket.value)
+    }
+
+    /// Get the entry whose key `f` reports equal. `f` compares a stored key
+    /// against the target, it should return `Less` for keys before the target
+    /// and be consistent with the order of the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(("alice", 3), 1);
+    /// sm.insert(("bob", 1), 2);
+    ///
+    /// // search by the name only
+    /// assert_eq!(sm.get_by(|k| k.0.cmp("bob")), Some((&("bob", 1), &2)));
+    /// assert_eq!(sm.get_by(|k| k.0.cmp("carol")), None);
+    /// ```
+    pub fn get_by<F>(&self, mut f: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K) -> Ordering,
+    {
+        self.sk
+            .sk
+            ._get_first_by(|bucket| f(&bucket.key))
+            .map(|(_, bucket)| (&bucket.key, &bucket.value))
+    }
+
+    /// Get values of the keys, the map is walked once for ascending keys.
+    /// Keys in other orders are still answered correctly, but not faster than
+    /// getting them one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i * 2, i);
+    /// }
+    ///
+    /// assert_eq!(sm.get_batch(&[2, 3, 8]), vec![Some(&1), None, Some(&4)]);
+    /// assert_eq!(sm.contains_batch(&[2, 3, 8]), vec![true, false, true]);
+    /// ```
+    pub fn get_batch<'q, Q: 'q + ?Sized, I>(&self, sorted_keys: I) -> Vec<Option<&V>>
+    where
+        I: IntoIterator<Item = &'q Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut seek_path = SeekPath::new(&self.sk.sk);
+        sorted_keys
+            .into_iter()
+            .map(|q| {
+                seek_path
+                    .get_first_by(|bucket| bucket.key.borrow().cmp(q))
+                    .map(|(_, bucket)| &bucket.value)
+            })
+            .collect()
+    }
+
+    /// Check if the map contains the keys, see [`SkipMap::get_batch`]
+    pub fn contains_batch<'q, Q: 'q + ?Sized, I>(&self, sorted_keys: I) -> Vec<bool>
+    where
+        I: IntoIterator<Item = &'q Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.get_batch(sorted_keys)
+            .into_iter()
+            .map(|found| found.is_some())
+            .collect()
+    }
+
+    /// Get the value of the key, the bloom filter is asked first and the map is
+    /// searched only if the key may be in it. Same as [`SkipMap::get`] if the map
+    /// has no filter.
+    #[cfg(feature = "bloom")]
+    pub fn get_filtered<Q: ?Sized>(&self, q: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + Hash,
+    {
+        match self.filter.as_ref() {
+            Some(filter) if !filter.may_contain_hash(bloom::hash_of(q)) => None,
+            _ => self.get(q),
+        }
+    }
+
+    /// Check if the map contains the key, see [`SkipMap::get_filtered`]
+    #[cfg(feature = "bloom")]
+    pub fn contains_key_filtered<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + Hash,
+    {
+        self.get_filtered(q).is_some()
+    }
+
+    /// Get the key-value pair of the key
+    pub fn get_key_value<Q: ?Sized>(&self, q: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk
+            .get_first(q)
+            .map(|(_, bucket)| (&bucket.key, &bucket.value))
+    }
+
+    /// Get the key-value pair of the key with the entries before and after it,
+    /// with one search. Returns `None` if the key doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// // free intervals by start
+    /// let mut free = SkipMap::new();
+    /// free.insert(0, 10);
+    /// free.insert(10, 15);
+    /// free.insert(20, 30);
+    ///
+    /// let (prev, (start, end), next) = free.get_with_neighbors(&10).unwrap();
+    /// assert_eq!(prev, Some((&0, &10)));
+    /// assert_eq!((*start, *end), (10, 15));
+    /// assert_eq!(next, Some((&20, &30)));
+    /// assert_eq!(free.get_with_neighbors(&0).unwrap().0, None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn get_with_neighbors<Q: ?Sized>(
+        &self,
+        q: &Q,
+    ) -> Option<(Option<(&K, &V)>, (&K, &V), Option<(&K, &V)>)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let node_ptr = self._find_ptr(q)?;
+        // Safety: node_ptr is a normal node of the map, and its prev is a
+        // normal node or head, whose value is None.
+        let node = unsafe { &*node_ptr };
+        let prev = unsafe { &*node.prev }.value.as_ref();
+        let next = node.next.as_ref().and_then(|next| next.value.as_ref());
+        let bucket = node.value.as_ref().expect("normal node always has a value");
+        Some((
+            prev.map(|b| (&b.key, &b.value)),
+            (&bucket.key, &bucket.value),
+            next.map(|b| (&b.key, &b.value)),
+        ))
+    }
+
+    /// Get the mutable value of the key, the entry is refreshed if the map
+    /// tracks access order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, 1);
+    /// *sm.get_mut(&1).unwrap() += 1;
+    /// assert_eq!(sm.get(&1), Some(&2));
+    /// ```
+    pub fn get_mut<Q: ?Sized>(&mut self, q: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let node_ptr = self._find_ptr(q)?;
+        self._touch_ptr(node_ptr);
+        // Safety: _find_ptr returns a valid normal node
+        unsafe { &mut *node_ptr }
+            .value
+            .as_mut()
+            .map(|bucket| &mut bucket.value)
+    }
+
+    /// Returns the entry of the smallest key, it's O(1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// assert_eq!(sm.first_key_value(), None);
+    /// sm.insert(2, "b");
+    /// sm.insert(1, "a");
+    /// assert_eq!(sm.first_key_value(), Some((&1, &"a")));
+    /// ```
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.sk.front().map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    /// Returns the entry of the largest key, it's a positional search in
+    /// O(log n) because the skiplist keeps no tail pointer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(2, "b");
+    /// sm.insert(1, "a");
+    /// assert_eq!(sm.last_key_value(), Some((&2, &"b")));
+    /// ```
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.sk.back().map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    /// Returns the entry of the smallest key with a mutable value, the entry is
+    /// refreshed if the map tracks access order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, 1);
+    /// sm.insert(2, 2);
+    /// if let Some((_, v)) = sm.first_key_value_mut() {
+    ///     *v += 10;
+    /// }
+    /// assert_eq!(sm.get(&1), Some(&11));
+    /// ```
+    pub fn first_key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self._entry_mut_at(0)
+    }
+
+    /// Returns the entry of the largest key with a mutable value, the entry is
+    /// refreshed if the map tracks access order
+    pub fn last_key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self._entry_mut_at(self.len().checked_sub(1)?)
+    }
+
+    fn _entry_mut_at(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        if index >= self.len() {
+            return None;
+        }
+        let node_ptr = self.sk.sk._get_ptr(index) as *mut Node<Bucket<K, V>>;
+        self._touch_ptr(node_ptr);
+        // Safety: index is checked, _get_ptr returns a valid normal node
+        unsafe { &mut *node_ptr }
+            .value
+            .as_mut()
+            .map(|bucket| (&bucket.key, &mut bucket.value))
+    }
+
+    /// Check if the map contains the key
+    pub fn contains_key<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.contains_key(q)
+    }
+
+    /// Remove the key, returns its value if the key exists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, "a");
+    /// assert_eq!(sm.remove(&1), Some("a"));
+    /// assert_eq!(sm.remove(&1), None);
+    /// ```
+    pub fn remove<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.remove_entry(q).map(|(_, v)| v)
+    }
+
+    /// Remove the key, returns the key-value pair if the key exists
+    pub fn remove_entry<Q: ?Sized>(&mut self, q: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let index = self.sk.get_first(q)?.0;
+        Some(self._remove_at(index))
+    }
+
+    /// Remove entries from the smallest key while `f` returns true for them,
+    /// returns removed entries in ascending order of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for (ts, event) in [(1, "a"), (3, "b"), (7, "c"), (9, "d")].iter() {
+    ///     sm.insert(*ts, *event);
+    /// }
+    ///
+    /// let now = 10;
+    /// let expired = sm.remove_while_front(|ts, _| now - ts > 5);
+    /// assert_eq!(expired, vec![(1, "a"), (3, "b")]);
+    /// assert_eq!(sm.len(), 2);
+    /// ```
+    pub fn remove_while_front<F>(&mut self, mut f: F) -> Vec<(K, V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut removed = vec![];
+        while let Some(bucket) = self.sk.get(0) {
+            if !f(&bucket.key, &bucket.value) {
+                break;
+            }
+            removed.push(self._remove_at(0));
+        }
+        removed
+    }
+
+    /// Remove entries from the largest key while `f` returns true for them,
+    /// returns removed entries in descending order of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// let removed = sm.remove_while_back(|k, _| *k >= 8);
+    /// assert_eq!(removed, vec![(9, 90), (8, 80)]);
+    /// assert_eq!(sm.len(), 8);
+    /// ```
+    pub fn remove_while_back<F>(&mut self, mut f: F) -> Vec<(K, V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut removed = vec![];
+        while self.sk.len() > 0 {
+            let index = self.sk.len() - 1;
+            let bucket = self.sk.get(index).expect("index is less than the length");
+            if !f(&bucket.key, &bucket.value) {
+                break;
+            }
+            removed.push(self._remove_at(index));
+        }
+        removed
+    }
+
+    /// Remove the key, returns the key-value pair with the entry after it if the
+    /// key exists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, "a");
+    /// sm.insert(2, "b");
+    ///
+    /// assert_eq!(sm.remove_and_next(&1), Some(((1, "a"), Some((&2, &"b")))));
+    /// assert_eq!(sm.remove_and_next(&2), Some(((2, "b"), None)));
+    /// assert_eq!(sm.remove_and_next(&2), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn remove_and_next<Q: ?Sized>(&mut self, q: &Q) -> Option<((K, V), Option<(&K, &V)>)>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let index = self.sk.get_first(q)?.0;
+        let removed = self._remove_at(index);
+        let next = self.sk.get(index).map(|b| (&b.key, &b.value));
+        Some((removed, next))
+    }
+
+    /// Move the entry of `old_key` to `new_key` without reallocating it, returns
+    /// whether the rank of the entry changed. If the map already has an entry of
+    /// `new_key`, that entry is removed first. Returns false if `old_key` doesn't
+    /// exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(10, "a");
+    /// sm.insert(20, "b");
+    /// sm.insert(30, "c");
+    ///
+    /// assert!(!sm.update_key(&20, 25));
+    /// assert!(sm.update_key(&10, 40));
+    /// let keys: Vec<_> = sm.keys().cloned().collect();
+    /// assert_eq!(keys, vec![25, 30, 40]);
+    /// assert_eq!(sm.get(&40), Some(&"a"));
+    /// ```
+    pub fn update_key<Q: ?Sized>(&mut self, old_key: &Q, new_key: K) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let old_index = match self.sk.get_first(old_key) {
+            None => return false,
+            Some((index, _)) => index,
+        };
+
+        let mut index = old_index;
+        match self.sk.get_first::<K>(&new_key) {
+            Some((other_index, _)) if other_index == index => {
+                // the same entry, only replace the key
+                let node_ptr = self._find_ptr(old_key).expect("old key exists");
+                // Safety: _find_ptr returns a valid normal node, the bucket is
+                // borrowed once at a time
+                let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+                let key = std::mem::replace(&mut bucket.key, new_key);
+                self._filter_remove(&key);
+                let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+                self._filter_insert(&bucket.key);
+                self._notify(&key, &bucket.value, Event::Remove);
+                self._notify(&bucket.key, &bucket.value, Event::Insert);
+                return false;
+            }
+            Some((other_index, _)) => {
+                self._remove_at(other_index);
+                if other_index < index {
+                    index -= 1;
+                }
+            }
+            None => (),
+        }
+
+        let mut node = self
+            .sk
+            .sk
+            ._unlink_range(index, index + 1)
+            .expect("index is in bounds");
+        let bucket = node.value.as_mut().expect("normal node always has a value");
+        let key = std::mem::replace(&mut bucket.key, new_key);
+        self._filter_remove(&key);
+        let (node_ptr, _) = self
+            .sk
+            .sk
+            ._insert_node_by(node, false, |a, b| a.key.cmp(&b.key));
+        // the entry is only moved, it's not a removal and an insertion
+        self.sk.sk.metrics.uncount_move();
+
+        // Safety: node_ptr is the node just linked
+        let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+        self._notify(&key, &bucket.value, Event::Remove);
+        self._notify(&bucket.key, &bucket.value, Event::Insert);
+        let key = &bucket.key;
+        self._filter_insert(key);
+        let new_index = self.sk.get_first::<K>(key).expect("key is just inserted").0;
+        new_index != old_index
+    }
+
+    /// Returns length of the map
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Returns an iterator of the map in key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(2, "b");
+    /// sm.insert(1, "a");
+    ///
+    /// let pairs: Vec<_> = sm.iter().collect();
+    /// assert_eq!(pairs, vec![(&1, &"a"), (&2, &"b")]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.sk.iter(),
+        }
+    }
+
+    /// Returns an iterator of the map in key order, in which values are mutable.
+    /// Entries are not refreshed in the access order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            inner: self.sk.sk.iter_mut(),
+        }
+    }
+
+    /// Returns an iterator of keys in order
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.sk.iter(),
+        }
+    }
+
+    /// Returns a read-only set view of the keys, it answers membership and range
+    /// queries with the map's own structure
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// let keys = sm.keys_set();
+    /// assert!(keys.contains(&3));
+    /// assert!(!keys.contains(&10));
+    /// assert_eq!(keys.range(&7..).cloned().collect::<Vec<_>>(), vec![7, 8, 9]);
+    /// ```
+    pub fn keys_set(&self) -> KeySet<'_, K, V> {
+        KeySet { map: self }
+    }
+
+    /// Returns an iterator of values in key order
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.sk.iter(),
+        }
+    }
+
+    /// Returns a moved iterator of keys in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert("b".to_string(), 1);
+    /// sm.insert("a".to_string(), 2);
+    /// let keys: Vec<String> = sm.into_keys().collect();
+    /// assert_eq!(keys, vec!["a", "b"]);
+    /// ```
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys {
+            inner: self.into_iter(),
+        }
+    }
+
+    /// Returns a moved iterator of values in key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(2, vec!["b"]);
+    /// sm.insert(1, vec!["a"]);
+    /// let values: Vec<_> = sm.into_values().rev().collect();
+    /// assert_eq!(values, vec![vec!["b"], vec!["a"]]);
+    /// ```
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues {
+            inner: self.into_iter(),
+        }
+    }
+
+    /// Returns a map with keys mapped by `f`, entries are inserted one by one so
+    /// `f` may reorder keys, the value of the larger old key is kept if two keys
+    /// are mapped to the same one.
+    ///
+    /// The result is a plain map with the level config of this map, order
+    /// tracking, the bloom filter and watchers are not carried over.
+    ///
+    /// Use [`map_keys_monotonic`](#method.map_keys_monotonic) if `f` keeps the
+    /// order of keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, "a");
+    /// sm.insert(2, "b");
+    /// sm.insert(3, "c");
+    /// let rekeyed = sm.map_keys(|k| k % 2);
+    /// assert_eq!(rekeyed.iter().collect::<Vec<_>>(), vec![(&0, &"b"), (&1, &"c")]);
+    /// ```
+    pub fn map_keys<K2, F>(self, mut f: F) -> SkipMap<K2, V>
+    where
+        K2: Ord,
+        F: FnMut(K) -> K2,
+    {
+        let mut result = SkipMap::with_config(self.probability(), self.max_levels());
+        for (key, value) in self {
+            result.insert(f(key), value);
+        }
+        result
+    }
+
+    /// Returns a map with keys mapped by `f` in O(n), entries are linked in one
+    /// splice without being compared.
+    ///
+    /// `f` must be strictly increasing, e.g. widening integer keys or
+    /// prefixing keys with the same namespace. It's only verified in debug
+    /// builds, the map is broken otherwise.
+    ///
+    /// Same as [`map_keys`](#method.map_keys), the result is a plain map with
+    /// the level config of this map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert("a".to_string(), 1u32);
+    /// sm.insert("b".to_string(), 2u32);
+    /// let namespaced = sm.map_keys_monotonic(|k| format!("user/{}", k));
+    /// assert_eq!(namespaced.get("user/b"), Some(&2));
+    /// ```
+    pub fn map_keys_monotonic<K2, F>(self, mut f: F) -> SkipMap<K2, V>
+    where
+        K2: Ord,
+        F: FnMut(K) -> K2,
+    {
+        let mut result = SkipMap::with_config(self.probability(), self.max_levels());
+        result
+            .sk
+            .sk
+            .extend_front(self.into_iter().map(|(k, v)| Bucket::new(f(k), v)));
+        debug_assert!(
+            result.keys().zip(result.keys().skip(1)).all(|(a, b)| a < b),
+            "f should be strictly increasing"
+        );
+        result
+    }
+
+    /// Returns a range iterator of entries whose keys are in the range
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// let values: Vec<_> = sm.range(&3..&6).map(|(_, v)| *v).collect();
+    /// assert_eq!(values, vec![30, 40, 50]);
+    /// ```
+    pub fn range<R, Q: ?Sized>(&self, range: R) -> Range<'_, K, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Range {
+            inner: self.sk.range(range),
+        }
+    }
+
+    /// Returns whether any key is in the range, with one search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// // booked time slots
+    /// let mut bookings = SkipMap::new();
+    /// bookings.insert(900, "standup");
+    /// bookings.insert(1400, "review");
+    ///
+    /// assert!(bookings.any_in_range(&1300..&1500));
+    /// assert!(!bookings.any_in_range(&1000..&1400));
+    /// ```
+    pub fn any_in_range<R, Q: ?Sized>(&self, range: R) -> bool
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.any_in_range(range)
+    }
+
+    /// Returns the maximal intervals in the range that contain no key, in
+    /// order, computed by walking the keys in the range once.
+    ///
+    /// Keys are treated as continuous, so an interval between adjacent integer
+    /// keys like `(Excluded(3), Excluded(4))` is still returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound::*;
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// // used ports
+    /// let mut used = SkipMap::new();
+    /// used.insert(8080, "web");
+    /// used.insert(8443, "tls");
+    ///
+    /// assert_eq!(
+    ///     used.gaps(8000..9000),
+    ///     vec![
+    ///         (Included(8000), Excluded(8080)),
+    ///         (Excluded(8080), Excluded(8443)),
+    ///         (Excluded(8443), Excluded(9000)),
+    ///     ]
+    /// );
+    /// assert_eq!(used.gaps(8080..=8080), vec![]);
+    /// ```
+    pub fn gaps<R>(&self, range: R) -> Vec<(Bound<K>, Bound<K>)>
+    where
+        R: ValueRange<K>,
+        K: Clone,
+    {
+        fn is_empty<K: Ord>(low: &Bound<K>, high: &Bound<K>) -> bool {
+            match (low, high) {
+                (Bound::Included(low), Bound::Included(high)) => low > high,
+                (Bound::Included(low), Bound::Excluded(high))
+                | (Bound::Excluded(low), Bound::Included(high))
+                | (Bound::Excluded(low), Bound::Excluded(high)) => low >= high,
+                _ => false,
+            }
+        }
+
+        let mut gaps = vec![];
+        let mut low = range.start_bound().cloned();
+        for bucket in self.sk.range((range.start_bound(), range.end_bound())) {
+            let high = Bound::Excluded(bucket.key.clone());
+            if !is_empty(&low, &high) {
+                gaps.push((low, high));
+            }
+            low = Bound::Excluded(bucket.key.clone());
+        }
+        let high = range.end_bound().cloned();
+        if !is_empty(&low, &high) {
+            gaps.push((low, high));
+        }
+        gaps
+    }
+
+    /// Returns graph that contains entries whose keys are in the range, entries
+    /// are shown as `key: value`. See
+    /// [`SkipList::explain`](../skiplist/struct.SkipList.html#method.explain)
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..100 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// let graph = sm.explain_by_value(&42..=&44).unwrap();
+    /// assert!(graph.starts_with("start: 42, levels: "));
+    /// assert!(graph.contains("43: 430"));
+    /// ```
+    pub fn explain_by_value<R, Q: ?Sized>(&self, range: R) -> Result<String, &'static str>
+    where
+        K: std::fmt::Display + Borrow<Q>,
+        V: std::fmt::Display,
+        R: ValueRange<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self.sk._key_range_to_index_range(range);
+        self.sk.sk.explain(left..right)
+    }
+
+    /// Returns a map with clones of the entries whose keys are in the range, the
+    /// entries are cloned in one traversal and linked in one splice. The new map
+    /// doesn't track order or keep a bloom filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// let part = sm.clone_range(&3..=&4);
+    /// assert_eq!(part.iter().collect::<Vec<_>>(), vec![(&3, &30), (&4, &40)]);
+    /// ```
+    pub fn clone_range<R, Q: ?Sized>(&self, range: R) -> SkipMap<K, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q> + Clone,
+        V: Clone,
+        Q: Ord,
+    {
+        let mut result = SkipMap::with_level_generator(self.sk.sk.level_generator.clone());
+        result.sk.sk._insert_iter_at(
+            0,
+            self.range(range)
+                .map(|(k, v)| Bucket::new(k.clone(), v.clone())),
+        );
+        result
+    }
+
+    /// Returns a range iterator of entries whose keys are in the range, in which
+    /// values are mutable
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..10 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    ///
+    /// for (_, v) in sm.range_mut(&3..&6) {
+    ///     *v += 1;
+    /// }
+    /// let values: Vec<_> = sm.values().cloned().collect();
+    /// assert_eq!(values, vec![0, 10, 20, 31, 41, 51, 60, 70, 80, 90]);
+    /// ```
+    pub fn range_mut<R, Q: ?Sized>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self.sk._key_range_to_index_range(range);
+        RangeMut {
+            inner: self.sk.sk.range_mut(left..right),
+        }
+    }
+
+    /// Returns at most `limit` entries starting from the index `offset`, and the
+    /// last key of them as the continuation token if more entries follow
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// for i in 0..5 {
+    ///     sm.insert(i, i * 10);
+    /// }
+    /// assert_eq!(sm.page(1, 2), (vec![(&1, &10), (&2, &20)], Some(&2)));
+    /// assert_eq!(sm.page_after(&2, 5), (vec![(&3, &30), (&4, &40)], None));
+    /// ```
+    pub fn page(&self, offset: usize, limit: usize) -> (Vec<(&K, &V)>, Option<&K>) {
+        self._page(offset, limit)
+    }
+
+    /// Returns at most `limit` entries whose keys are greater than q, and the
+    /// last key of them as the continuation token if more entries follow
+    pub fn page_after<Q: ?Sized>(&self, q: &Q, limit: usize) -> (Vec<(&K, &V)>, Option<&K>)
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (offset, _) = self
+            .sk
+            ._key_range_to_index_range((Bound::Excluded(q), Bound::Unbounded));
+        self._page(offset, limit)
+    }
+
+    fn _page(&self, offset: usize, limit: usize) -> (Vec<(&K, &V)>, Option<&K>) {
+        let left = offset.min(self.len());
+        let right = offset.saturating_add(limit).min(self.len());
+        let items: Vec<(&K, &V)> = Range {
+            inner: self.sk.sk.range(left..right),
+        }
+        .collect();
+        let next = if right < self.len() {
+            items.last().map(|(k, _)| *k)
+        } else {
+            None
+        };
+        (items, next)
+    }
+
+    /// Watch changes of entries whose keys are in the range. Every insert,
+    /// update and removal made afterwards through the map's methods is sent to
+    /// the receiver, the watch is dropped with the receiver.
+    ///
+    /// Values changed in place through `get_mut`, `iter_mut` or `range_mut`
+    /// are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::{Event, SkipMap};
+    ///
+    /// let mut sm = SkipMap::new();
+    /// let events = sm.watch_range(&10..&20);
+    /// sm.insert(5, "a");
+    /// sm.insert(15, "b");
+    /// sm.insert(15, "c");
+    /// sm.remove(&15);
+    ///
+    /// let events: Vec<_> = events.try_iter().collect();
+    /// assert_eq!(
+    ///     events,
+    ///     vec![
+    ///         Event::Insert(15, "b"),
+    ///         Event::Update(15, "c"),
+    ///         Event::Remove(15, "c"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn watch_range<R>(&mut self, range: R) -> Receiver<Event<K, V>>
+    where
+        R: ValueRange<K>,
+        K: Clone,
+        V: Clone,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.watchers.push(Watcher {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+            sender,
+            clone_key: K::clone,
+            clone_value: V::clone,
+        });
+        receiver
+    }
+
+    /// Returns an iterator of entries from the oldest to the newest.
+    /// It yields nothing if the map doesn't track order.
+    pub fn iter_insertion_order(&self) -> OrderIter<'_, K, V> {
+        OrderIter {
+            current: self.oldest,
+            left: if self.oldest.is_null() { 0 } else { self.len() },
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Remove the oldest entry, returns None if the map is empty or the map
+    /// doesn't track order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::{OrderTracking, SkipMap};
+    ///
+    /// let mut sm = SkipMap::with_order_tracking(OrderTracking::Access);
+    /// sm.insert(1, "a");
+    /// sm.insert(2, "b");
+    /// sm.get_mut(&1);
+    /// assert_eq!(sm.pop_oldest(), Some((2, "b")));
+    /// assert_eq!(sm.pop_oldest(), Some((1, "a")));
+    /// assert_eq!(sm.pop_oldest(), None);
+    /// ```
+    pub fn pop_oldest(&mut self) -> Option<(K, V)> {
+        if self.oldest.is_null() {
+            return None;
+        }
+
+        // Safety: oldest is checked that it won't be null, and it's a normal node
+        let key = &unsafe { &*self.oldest }
+            .value
+            .as_ref()
+            .expect("normal node always has a value")
+            .key;
+        let index = self.sk.get_first(key)?.0;
+        Some(self._remove_at(index))
+    }
+
+    /// Mark the entry of the key as the newest one if the map tracks access order,
+    /// returns whether the key exists.
+    pub fn touch<Q: ?Sized>(&mut self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        match self._find_ptr(q) {
+            None => false,
+            Some(node_ptr) => {
+                self._touch_ptr(node_ptr);
+                true
+            }
+        }
+    }
+
+    fn _find_ptr<Q: ?Sized>(&self, q: &Q) -> Option<*mut Node<Bucket<K, V>>>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (prev_ptr, _) = self
+            .sk
+            .sk
+            ._seek_by(|bucket| bucket.key.borrow().cmp(q), false);
+        // Safety: prev_ptr will never be null and always valid.
+        let next = unsafe { &*prev_ptr }.next.as_ref()?;
+        let bucket = next.value.as_ref().expect("normal node always has a value");
+        if bucket.key.borrow() == q {
+            Some(&**next as *const _ as *mut _)
+        } else {
+            None
+        }
+    }
+
+    /// Insert an entry whose key is not in the map, link it to the order list
+    fn _insert_new(&mut self, key: K, value: V) -> *mut Node<Bucket<K, V>> {
+        let (node_ptr, _) =
+            self.sk
+                .sk
+                ._insert_by(Bucket::new(key, value), false, |a, b| a.key.cmp(&b.key));
+        if self.order_tracking.is_some() {
+            self._link_newest(node_ptr);
+        }
+        // Safety: node_ptr is the node just linked
+        let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+        self._filter_insert(&bucket.key);
+        self._notify(&bucket.key, &bucket.value, Event::Insert);
+        node_ptr
+    }
+
+    /// Remove the entry at the index and unlink it from the order list
+    pub(crate) fn _remove_at(&mut self, index: usize) -> (K, V) {
+        let bucket = self.sk.remove(index);
+        if self.order_tracking.is_some() {
+            self._unlink(bucket.older, bucket.newer);
+        }
+        self._filter_remove(&bucket.key);
+        self._notify(&bucket.key, &bucket.value, Event::Remove);
+        (bucket.key, bucket.value)
+    }
+
+    /// Send the event to every watcher of the key, watchers whose receiver is
+    /// dropped are removed
+    fn _notify(&mut self, key: &K, value: &V, event: fn(K, V) -> Event<K, V>) {
+        self.watchers.retain(|watcher| {
+            if !watcher.contains(key) {
+                return true;
+            }
+            let event = event((watcher.clone_key)(key), (watcher.clone_value)(value));
+            watcher.sender.send(event).is_ok()
+        });
+    }
+
+    // Every key added to or removed from the map should go through these, they
+    // do nothing if the map has no bloom filter.
+
+    fn _filter_insert(&mut self, _key: &K) {
+        #[cfg(feature = "bloom")]
+        if let Some(filter) = self.filter.as_mut() {
+            filter.insert_hash(filter.hash(_key));
+        }
+    }
+
+    fn _filter_remove(&mut self, _key: &K) {
+        #[cfg(feature = "bloom")]
+        if let Some(filter) = self.filter.as_mut() {
+            filter.remove_hash(filter.hash(_key));
+        }
+    }
+
+    fn _touch_ptr(&mut self, node_ptr: *mut Node<Bucket<K, V>>) {
+        if self.order_tracking != Some(OrderTracking::Access) || node_ptr == self.newest {
+            return;
+        }
+
+        let (older, newer) = {
+            // Safety: callers pass a normal node of the map
+            let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+            (bucket.older, bucket.newer)
+        };
+        self._unlink(older, newer);
+        self._link_newest(node_ptr);
+    }
+
+    fn _link_newest(&mut self, node_ptr: *mut Node<Bucket<K, V>>) {
+        // Safety: callers pass a normal node of the map, and pointers in the
+        // order list always point to normal nodes of the map
+        let bucket = unsafe { Self::_bucket_mut(node_ptr) };
+        bucket.older = self.newest;
+        bucket.newer = std::ptr::null_mut();
+        if self.newest.is_null() {
+            self.oldest = node_ptr;
+        } else {
+            unsafe { Self::_bucket_mut(self.newest) }.newer = node_ptr;
+        }
+        self.newest = node_ptr;
+    }
+
+    fn _unlink(&mut self, older: *mut Node<Bucket<K, V>>, newer: *mut Node<Bucket<K, V>>) {
+        if older.is_null() {
+            self.oldest = newer;
+        } else {
+            // Safety: pointers in the order list always point to normal nodes
+            unsafe { Self::_bucket_mut(older) }.newer = newer;
+        }
+
+        if newer.is_null() {
+            self.newest = older;
+        } else {
+            // Safety: pointers in the order list always point to normal nodes
+            unsafe { Self::_bucket_mut(newer) }.older = older;
+        }
+    }
+
+    /// Returns the bucket of a node
+    ///
+    /// # Safety
+    ///
+    /// `node_ptr` must point to a normal node of the map, the node must outlive
+    /// the returned reference, and no other reference to the bucket may be alive
+    /// while it is used.
+    unsafe fn _bucket_mut<'a>(node_ptr: *mut Node<Bucket<K, V>>) -> &'a mut Bucket<K, V> {
+        (*node_ptr)
+            .value
+            .as_mut()
+            .expect("normal node always has a value")
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for SkipMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for SkipMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for SkipMap<K, V> {}
+
+impl<K: Ord + std::hash::Hash, V: std::hash::Hash> std::hash::Hash for SkipMap<K, V> {
+    /// Hash the length and entries in key order, equal maps have equal hashes
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for entry in self.iter() {
+            entry.hash(state);
+        }
+    }
+}
+
+impl<K: Ord, V> IntoIterator for SkipMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    /// Returns a moved iterator of the map in key order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, "a");
+    /// sm.insert(0, "b");
+    /// let pairs: Vec<_> = sm.into_iter().collect();
+    /// assert_eq!(pairs, vec![(0, "b"), (1, "a")]);
+    ///
+    /// let mut sm = SkipMap::new();
+    /// sm.insert(1, "a");
+    /// sm.insert(0, "b");
+    /// let top = sm.into_iter().next_back();
+    /// assert_eq!(top, Some((1, "a")));
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.sk.into_iter(),
+        }
+    }
+}
+
+impl<V: Ord> From<SkipSet<V>> for SkipMap<V, ()> {
+    /// Convert a set to a map with empty values. The values are linked in key
+    /// order in one splice without being compared, the bloom filter of the set
+    /// is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipmap::SkipMap;
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(0..3);
+    /// let mut sm = SkipMap::from(set);
+    /// assert_eq!(sm.get(&1), Some(&()));
+    /// sm.insert(3, ());
+    /// assert_eq!(SkipSet::from(sm).cardinal(), 4);
+    /// ```
+    fn from(set: SkipSet<V>) -> Self {
+        SkipMap::from_sorted_iter_unchecked(set.into_iter().map(|v| (v, ())))
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    inner: skiplist::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for Iter<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+pub struct IterMut<'a, K, V> {
+    inner: skiplist::IterMut<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|bucket| (&bucket.key, &mut bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+pub struct Keys<'a, K, V> {
+    inner: skiplist::Iter<'a, Bucket<K, V>>,
+}
 
-    fn new() -> Self { unimplemented!() }
+impl<'a, K, V> Clone for Keys<'a, K, V> {
+    fn clone(&self) -> Self {
+        Keys {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K: std::fmt::Debug, V> std::fmt::Debug for Keys<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bucket| &bucket.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+/// A read-only set view of the keys of a map, see [`SkipMap::keys_set`]
+pub struct KeySet<'a, K: Ord, V> {
+    map: &'a SkipMap<K, V>,
+}
+
+impl<'a, K: Ord, V> Clone for KeySet<'a, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, K: Ord, V> Copy for KeySet<'a, K, V> {}
+
+impl<'a, K: Ord, V> KeySet<'a, K, V> {
+    /// Returns number of keys
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the key is in the set
+    pub fn contains<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.contains_key(q)
+    }
+
+    /// Get the key in the set that equals q
+    pub fn get<Q: ?Sized>(&self, q: &Q) -> Option<&'a K>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.map.get_key_value(q).map(|(k, _)| k)
+    }
+
+    /// Returns an iterator of keys in order
+    pub fn iter(&self) -> Keys<'a, K, V> {
+        self.map.keys()
+    }
+
+    /// Returns an iterator of keys in the range
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    pub fn range<R, Q: ?Sized>(&self, range: R) -> RangeKeys<'a, K, V>
+    where
+        R: ValueRange<Q>,
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        RangeKeys {
+            inner: self.map.range(range),
+        }
+    }
+}
+
+impl<'a, K: Ord + std::fmt::Debug, V> std::fmt::Debug for KeySet<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for KeySet<'a, K, V> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct RangeKeys<'a, K, V> {
+    inner: Range<'a, K, V>,
+}
+
+impl<'a, K, V> Clone for RangeKeys<'a, K, V> {
+    fn clone(&self) -> Self {
+        RangeKeys {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for RangeKeys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for RangeKeys<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for RangeKeys<'a, K, V> {}
+
+pub struct Values<'a, K, V> {
+    inner: skiplist::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Clone for Values<'a, K, V> {
+    fn clone(&self) -> Self {
+        Values {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K, V: std::fmt::Debug> std::fmt::Debug for Values<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bucket| &bucket.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+pub struct Range<'a, K, V> {
+    inner: skiplist::Range<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Clone for Range<'a, K, V> {
+    fn clone(&self) -> Self {
+        Range {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<'a, K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for Range<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Range<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Range<'a, K, V> {}
+
+pub struct RangeMut<'a, K, V> {
+    inner: skiplist::RangeMut<'a, Bucket<K, V>>,
+}
 
-    fn insert(&mut self, key: K, value: V) -> Option<(K, V)> { unimplemented!() }
+impl<'a, K, V> Iterator for RangeMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
 
-    fn remove<Q: ?Sized>(&mut self, q: &Q) -> Option<(K, V)>
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|bucket| (&bucket.key, &mut bucket.value))
+    }
 
-    fn get<Q: ?Sized>(&self, q: &Q) -> Option<&V>
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for RangeMut<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for RangeMut<'a, K, V> {}
+
+pub struct IntoIter<K, V> {
+    inner: skiplist::IntoIter<Bucket<K, V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|bucket| (bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|bucket| (bucket.key, bucket.value))
+    }
+}
 
-    fn get_mut<Q: ?Sized>(&mut self, q: &Q) -> Option<&mut V>
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+impl<K, V> FusedIterator for IntoIter<K, V> {}
 
-    fn get_kv<Q: ?Sized>(&self, q: &Q) -> Option<&K, &V>
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
 
-    fn get_kv_mut<Q: ?Sized>(&mut self, q: &Q) -> Option<&K, &mut V>
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+pub struct IntoKeys<K, V> {
+    inner: IntoIter<K, V>,
+}
 
-    fn contains<Q: ?Sized>(&self, q: &Q) -> bool
-    where K: Borrowed<Q>,
-          Q: Ord
-    { unimplemented!() }
+impl<K, V> Iterator for IntoKeys<K, V> {
+    type Item = K;
 
-    fn len(&self) -> usize { unimplemented!() }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
 
-    fn first(&self) -> Option<&K, &V> { unimplemented!() }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
 
-    fn first_mut(&mut self) -> Option<&K, &mut V> { unimplemented!() }
+impl<K, V> DoubleEndedIterator for IntoKeys<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
 
-    fn remove_first(&mut self) -> Option<K, V> { unimplemented!() }
+impl<K, V> FusedIterator for IntoKeys<K, V> {}
 
-    fn last(&self) -> Option<&K, &V> { unimplemented!() }
+impl<K, V> ExactSizeIterator for IntoKeys<K, V> {}
 
-    fn last_mut(&mut self) -> Option<&K, &mut V> { unimplemented!() }
+pub struct IntoValues<K, V> {
+    inner: IntoIter<K, V>,
+}
 
-    fn remove_last(&mut self) -> Option<K, V> { unimplemented!() }
+impl<K, V> Iterator for IntoValues<K, V> {
+    type Item = V;
 
-    fn iter(&self) -> Iter<'_, K, V> { unimplemented!() }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
 
-    fn into_iter(self) -> IntoIter<K, V> { unimplemented!() }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoValues<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> FusedIterator for IntoValues<K, V> {}
+
+impl<K, V> ExactSizeIterator for IntoValues<K, V> {}
+
+/// An iterator of the entries of a `SkipMap` from the oldest to the newest.
+///
+/// This `struct` is created by the [`iter_insertion_order`] method on
+/// [`SkipMap`]. See its documentation for more.
+///
+/// [`SkipMap`]: struct.SkipMap.html
+/// [`iter_insertion_order`]: struct.SkipMap.html#method.iter_insertion_order
+pub struct OrderIter<'a, K, V> {
+    current: *const Node<Bucket<K, V>>,
+    left: usize,
+    phantom: std::marker::PhantomData<&'a Bucket<K, V>>,
+}
+
+impl<'a, K, V> Clone for OrderIter<'a, K, V> {
+    fn clone(&self) -> Self {
+        OrderIter {
+            current: self.current,
+            left: self.left,
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for OrderIter<'a, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for OrderIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() {
+            return None;
+        }
+
+        // Safety: `current` won't be null when the program run to here,
+        // pointers in the order list always point to normal nodes of the map
+        let bucket = unsafe { &*self.current }
+            .value
+            .as_ref()
+            .expect("normal node always has a value");
+        self.current = bucket.newer;
+        self.left -= 1;
+        Some((&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
 
-    fn iter_mut(&mut self) -> IterMut<'_, K, V> { unimplemented!() }
+impl<'a, K, V> ExactSizeIterator for OrderIter<'a, K, V> {}
 
-    fn range(&self) -> Range<'_, K, V> { unimplemented!() }
+impl<'a, K, V> FusedIterator for OrderIter<'a, K, V> {}
 
-    fn range_mut(&mut self) -> RangeMut<'_, K, V> { unimplemented!() }
-}
\ No newline at end of file
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_and_last_key_value() {
+        let mut sm = SkipMap::with_order_tracking(OrderTracking::Access);
+        assert_eq!(sm.first_key_value_mut(), None);
+        assert_eq!(sm.last_key_value_mut(), None);
+        for i in (0..100).rev() {
+            sm.insert(i, i);
+        }
+        assert_eq!(sm.first_key_value(), Some((&0, &0)));
+        assert_eq!(sm.last_key_value(), Some((&99, &99)));
+
+        *sm.last_key_value_mut().unwrap().1 = 100;
+        *sm.first_key_value_mut().unwrap().1 = -1;
+        assert_eq!(sm.get(&99), Some(&100));
+        assert_eq!(sm.get(&0), Some(&-1));
+        let newest: Vec<_> = sm.iter_insertion_order().skip(98).map(|(k, _)| *k).collect();
+        assert_eq!(newest, vec![99, 0]);
+    }
+
+    #[test]
+    fn map_keys() {
+        let mut sm = SkipMap::new();
+        for i in 0..100u32 {
+            sm.insert(i, i);
+        }
+
+        let widened = sm.map_keys_monotonic(|k| k as u64 * 3);
+        assert_eq!(widened.len(), 100);
+        assert_eq!(widened.get(&297), Some(&99));
+        widened.sk.sk._check_integrity();
+
+        let reversed = widened.map_keys(|k| -(k as i64));
+        assert!(reversed.iter().map(|(_, v)| *v).eq((0..100).rev()));
+        reversed.sk.sk._check_integrity();
+    }
+
+    #[test]
+    fn gaps() {
+        use std::ops::Bound::*;
+
+        let mut sm = SkipMap::new();
+        assert_eq!(sm.gaps(..), vec![(Unbounded, Unbounded)]);
+        for i in 1..4 {
+            sm.insert(i * 10, ());
+        }
+        assert_eq!(
+            sm.gaps(..),
+            vec![
+                (Unbounded, Excluded(10)),
+                (Excluded(10), Excluded(20)),
+                (Excluded(20), Excluded(30)),
+                (Excluded(30), Unbounded),
+            ]
+        );
+        assert_eq!(
+            sm.gaps(10..=30),
+            vec![(Excluded(10), Excluded(20)), (Excluded(20), Excluded(30))]
+        );
+        assert_eq!(sm.gaps(15..20), vec![(Included(15), Excluded(20))]);
+        assert_eq!(sm.gaps((Excluded(&20), Included(&25))), vec![(Excluded(20), Included(25))]);
+        assert_eq!(sm.gaps(20..20), vec![]);
+    }
+
+    #[test]
+    fn hash_and_eq() {
+        use std::collections::HashSet;
+
+        let mut maps = HashSet::new();
+        let mut sets = HashSet::new();
+        for i in 0..10 {
+            let mut sm = SkipMap::new();
+            let mut ss = SkipSet::new();
+            for k in (0..i).rev() {
+                sm.insert(k, k * 2);
+                ss.add(k);
+            }
+            maps.insert(sm);
+            sets.insert(ss);
+        }
+        assert_eq!(maps.len(), 10);
+        assert_eq!(sets.len(), 10);
+
+        let mut sm = SkipMap::new();
+        let mut ss = SkipSet::new();
+        for k in 0..5 {
+            sm.insert(k, k * 2);
+            ss.add(k);
+        }
+        assert!(maps.contains(&sm));
+        assert!(sets.contains(&ss));
+        sm.insert(4, 0);
+        assert!(!maps.contains(&sm));
+        ss.add(5);
+        assert!(sets.contains(&ss));
+        ss.add(10);
+        assert!(!sets.contains(&ss));
+    }
+
+    #[test]
+    fn watch_range() {
+        let mut sm = SkipMap::new();
+        let events = sm.watch_range(..&10);
+        let dropped = sm.watch_range(..);
+        drop(dropped);
+
+        sm.insert(3, 'a');
+        sm.insert(12, 'b');
+        sm.insert_or_merge(3, 'c', |old, new| *old = new);
+        sm.update_key(&3, 11);
+        sm.update_key(&12, 1);
+        sm.remove(&11);
+        sm.remove(&1);
+        assert_eq!(sm.watchers.len(), 1);
+
+        let events: Vec<_> = events.try_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::Insert(3, 'a'),
+                Event::Update(3, 'c'),
+                Event::Remove(3, 'c'),
+                Event::Insert(1, 'b'),
+                Event::Remove(1, 'b'),
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut sm = SkipMap::new();
+        for i in (0..20).rev() {
+            assert_eq!(sm.insert(i, i * 2), None);
+        }
+        assert_eq!(sm.len(), 20);
+        assert_eq!(sm.insert(5, 0), Some(10));
+        assert_eq!(sm.get(&5), Some(&0));
+        assert_eq!(sm.remove(&5), Some(0));
+        assert_eq!(sm.get(&5), None);
+        assert_eq!(sm.len(), 19);
+        assert_eq!(sm.keys().cloned().collect::<Vec<_>>().len(), 19);
+        assert_eq!(format!("{:?}", sm.range(&1..&3).collect::<Vec<_>>()), "[(1, 2), (2, 4)]");
+    }
+
+    #[test]
+    fn map_keys_config() {
+        let mut sm = SkipMap::with_config(0.25, 8);
+        for i in 0..100 {
+            sm.insert(i, i);
+        }
+        let rekeyed = sm.map_keys(|k| k * 2);
+        assert_eq!((rekeyed.probability(), rekeyed.max_levels()), (0.25, 8));
+        assert_eq!(rekeyed.order_tracking(), None);
+        let rekeyed = rekeyed.map_keys_monotonic(|k| k + 1);
+        assert_eq!((rekeyed.probability(), rekeyed.max_levels()), (0.25, 8));
+        assert_eq!(rekeyed.get(&199), Some(&99));
+    }
+
+    #[test]
+    fn update_key() {
+        let mut sm = SkipMap::with_order_tracking(OrderTracking::Insertion);
+        for i in 0..10 {
+            sm.insert(i * 10, i);
+        }
+
+        assert!(!sm.update_key(&55, 1));
+        assert!(!sm.update_key(&50, 55));
+        assert!(sm.update_key(&0, 95));
+        assert!(sm.update_key(&90, 30));
+        assert_eq!(sm.len(), 9);
+        assert_eq!(sm.get(&30), Some(&9));
+        assert_eq!(sm.get(&95), Some(&0));
+
+        let keys: Vec<_> = sm.keys().cloned().collect();
+        assert_eq!(keys, vec![10, 20, 30, 40, 55, 60, 70, 80, 95]);
+        let keys: Vec<_> = sm.iter_insertion_order().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![95, 10, 20, 40, 55, 60, 70, 80, 30]);
+    }
+
+    #[test]
+    fn insertion_order() {
+        let mut sm = SkipMap::with_order_tracking(OrderTracking::Insertion);
+        for i in [5, 3, 8, 1, 9].iter() {
+            sm.insert(*i, ());
+        }
+        sm.insert(3, ());
+        sm.get_mut(&5);
+        sm.remove(&8);
+
+        let keys: Vec<_> = sm.iter_insertion_order().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![5, 3, 1, 9]);
+        let mut iter = sm.iter_insertion_order();
+        assert_eq!(iter.len(), 4);
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(SkipMap::<i32, ()>::new().iter_insertion_order().len(), 0);
+        let mut untracked = SkipMap::new();
+        untracked.insert(1, ());
+        assert_eq!(untracked.iter_insertion_order().len(), 0);
+
+        assert_eq!(sm.pop_oldest(), Some((5, ())));
+        assert_eq!(sm.pop_oldest(), Some((3, ())));
+        let keys: Vec<_> = sm.keys().cloned().collect();
+        assert_eq!(keys, vec![1, 9]);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn update_key_metrics() {
+        let mut sm = SkipMap::new();
+        for i in 0..10 {
+            sm.insert(i, i);
+        }
+        sm.reset_metrics();
+        assert!(sm.update_key(&2, 20));
+        let metrics = sm.metrics();
+        assert_eq!((metrics.inserts, metrics.removes), (0, 0));
+
+        // replacing an existing key still removes its entry
+        assert!(sm.update_key(&3, 5));
+        let metrics = sm.metrics();
+        assert_eq!((metrics.inserts, metrics.removes), (0, 1));
+    }
+
+    #[cfg(feature = "bloom")]
+    #[test]
+    fn bloom_filter() {
+        let mut sm = SkipMap::with_bloom_filter(100, 0.01);
+        for i in 0..100 {
+            sm.insert(i, i);
+        }
+        for i in 0..50 {
+            sm.remove(&i);
+        }
+        assert!(sm.update_key(&99, 0));
+        sm.insert_or_merge(200, 0, |_, _| ());
+
+        assert!(sm.contains_key_filtered(&0));
+        assert!(sm.contains_key_filtered(&200));
+        assert!(!sm.contains_key_filtered(&99));
+        for i in 1..400 {
+            assert_eq!(sm.get_filtered(&i), sm.get(&i));
+        }
+    }
+
+    #[test]
+    fn access_order() {
+        let mut sm = SkipMap::with_order_tracking(OrderTracking::Access);
+        for i in 0..5 {
+            sm.insert(i, i);
+        }
+        sm.insert(0, 10);
+        sm.get_mut(&2);
+        assert!(sm.touch(&1));
+        assert!(!sm.touch(&7));
+
+        let keys: Vec<_> = sm.iter_insertion_order().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![3, 4, 0, 2, 1]);
+
+        // iterating mutably doesn't count as access
+        for (_, v) in sm.iter_mut() {
+            *v += 1;
+        }
+        sm.range_mut(&3..).for_each(|(_, v)| *v += 1);
+        sm.last_key_value_mut();
+        let keys: Vec<_> = sm.iter_insertion_order().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![3, 0, 2, 1, 4]);
+
+        while sm.pop_oldest().is_some() {}
+        assert_eq!(sm.len(), 0);
+        assert_eq!(sm.iter_insertion_order().next(), None);
+    }
+}
@@ -0,0 +1,249 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+use std::ops::Bound;
+
+use crate::keyed_skiplist::KeyedSkipList;
+use crate::level_generator::LevelGenerator;
+use crate::skiplist;
+
+fn key_of<K, V>(entry: &(K, V)) -> &K {
+    &entry.0
+}
+
+/// A map that allows multiple values of a key, entries are ordered by keys and
+/// values of a key keep their insertion order.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::skipmultimap::SkipMultiMap;
+///
+/// // events indexed by timestamps which may collide
+/// let mut events = SkipMultiMap::new();
+/// events.insert(20, "deploy");
+/// events.insert(10, "build");
+/// events.insert(20, "notify");
+///
+/// assert_eq!(events.get_all(&20).collect::<Vec<_>>(), vec![&"deploy", &"notify"]);
+/// assert_eq!(events.count(&20), 2);
+/// assert_eq!(events.remove_all(&20), vec!["deploy", "notify"]);
+/// assert_eq!(events.len(), 1);
+/// ```
+pub struct SkipMultiMap<K: Ord, V> {
+    sk: KeyedSkipList<K, (K, V)>,
+}
+
+impl<K: Ord, V> SkipMultiMap<K, V> {
+    pub fn new() -> Self {
+        SkipMultiMap {
+            sk: KeyedSkipList::new_duplicatable(key_of::<K, V>),
+        }
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        SkipMultiMap {
+            sk: KeyedSkipList::with_config(
+                true,
+                LevelGenerator::with_config(p, max_levels),
+                key_of::<K, V>,
+            ),
+        }
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Returns number of entries in the map
+    pub fn len(&self) -> usize {
+        self.sk.len()
+    }
+
+    /// Insert an entry, it's put after entries of the same key
+    pub fn insert(&mut self, key: K, value: V) {
+        // entries of the same key are treated as less, so the new one goes after them
+        self.sk
+            .sk
+            ._insert_by((key, value), true, |a, b| a.0.cmp(&b.0).then(Ordering::Less));
+    }
+
+    /// Returns an iterator of values of the key in insertion order
+    pub fn get_all<Q: ?Sized>(&self, q: &Q) -> Values<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Values {
+            inner: self.sk.range((Bound::Included(q), Bound::Included(q))),
+        }
+    }
+
+    /// Returns the first value of the key
+    pub fn get_first<Q: ?Sized>(&self, q: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.get_first(q).map(|(_, entry)| &entry.1)
+    }
+
+    /// Returns number of values of the key, in O(log n)
+    pub fn count<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self
+            .sk
+            ._key_range_to_index_range((Bound::Included(q), Bound::Included(q)));
+        right - left
+    }
+
+    /// Check if the map contains the key
+    pub fn contains_key<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.contains_key(q)
+    }
+
+    /// Remove all entries of the key, returns their values in insertion order
+    pub fn remove_all<Q: ?Sized>(&mut self, q: &Q) -> Vec<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let (left, right) = self
+            .sk
+            ._key_range_to_index_range((Bound::Included(q), Bound::Included(q)));
+        self.sk.sk.drain_range(left..right).map(|(_, v)| v).collect()
+    }
+
+    /// Returns an iterator of entries ordered by keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.sk.iter(),
+        }
+    }
+}
+
+impl<K: Ord, V> Default for SkipMultiMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> std::iter::FromIterator<(K, V)> for SkipMultiMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipMultiMap::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for SkipMultiMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Values of a key in a [`SkipMultiMap`]
+pub struct Values<'a, K, V> {
+    inner: skiplist::Range<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+/// Entries of a [`SkipMultiMap`]
+pub struct Iter<'a, K, V> {
+    inner: skiplist::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values_of_keys() {
+        let mut map = SkipMultiMap::new();
+        for i in 0..1000 {
+            map.insert(i % 7, i);
+        }
+        assert_eq!(map.len(), 1000);
+        for key in 0..7 {
+            let values: Vec<_> = map.get_all(&key).cloned().collect();
+            let expected: Vec<_> = (0..1000).filter(|i| i % 7 == key).collect();
+            assert_eq!(values, expected);
+            assert_eq!(map.count(&key), expected.len());
+            assert_eq!(map.get_first(&key), Some(&key));
+        }
+        assert_eq!(map.count(&7), 0);
+        assert_eq!(map.get_all(&7).next(), None);
+
+        let removed = map.remove_all(&3);
+        assert_eq!(removed, (0..1000).filter(|i| i % 7 == 3).collect::<Vec<_>>());
+        assert!(!map.contains_key(&3));
+        assert_eq!(map.len(), 1000 - removed.len());
+        assert!(map.iter().zip(map.iter().skip(1)).all(|(a, b)| a.0 <= b.0));
+        map.sk.sk._check_integrity();
+    }
+
+    #[test]
+    fn level_config() {
+        let mut map = SkipMultiMap::with_config(0.25, 8);
+        assert_eq!(map.probability(), 0.25);
+        assert_eq!(map.max_levels(), 8);
+        map.set_probability(0.5);
+        assert_eq!(map.probability(), 0.5);
+        for i in 0..100 {
+            map.insert(i % 3, i);
+        }
+        assert_eq!(map.len(), 100);
+    }
+}
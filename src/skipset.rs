@@ -1,5 +1,6 @@
 use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::iter::FusedIterator;
 // use std::fmt::Display;
 use std::ops::RangeBounds;
 
@@ -7,12 +8,23 @@ use rand;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 
+#[cfg(feature = "bloom")]
+use std::hash::Hash;
+
+#[cfg(feature = "bloom")]
+use crate::bloom::{self, BloomFilter};
 use crate::level_generator::LevelGenerator;
-use crate::ordered_skiplist::OrderedSkipList;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::ordered_skiplist::{OrderedSkipList, SeekPath};
 use crate::skiplist::{IntoIter, Iter, Range};
+use crate::skipmap::SkipMap;
+use crate::value_range::ValueRange;
 
 pub struct SkipSet<V: Ord> {
     sk: OrderedSkipList<V>,
+    #[cfg(feature = "bloom")]
+    filter: Option<BloomFilter<V>>,
 }
 
 impl<V: Ord> SkipSet<V> {
@@ -23,9 +35,111 @@ impl<V: Ord> SkipSet<V> {
     pub fn with_level_generator(lg: LevelGenerator) -> Self {
         SkipSet {
             sk: OrderedSkipList::with_config(false, lg),
+            #[cfg(feature = "bloom")]
+            filter: None,
         }
     }
 
+    /// Create a set from values sorted in ascending order without duplicates, the
+    /// values are linked in one splice without being compared.
+    ///
+    /// The order is only verified in debug builds, the set is broken if the values
+    /// are not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let ss = SkipSet::from_sorted_iter_unchecked(0..10);
+    /// assert!(ss.contains(&3));
+    /// assert_eq!(ss.cardinal(), 10);
+    /// ```
+    pub fn from_sorted_iter_unchecked<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+    {
+        let mut ss = Self::new();
+        ss.sk.sk.extend_front(iter);
+        debug_assert!(
+            ss.iter().zip(ss.iter().skip(1)).all(|(a, b)| a < b),
+            "values should be sorted without duplicates"
+        );
+        ss
+    }
+
+    /// Create a set that maintains a bloom filter of its values, so absent values
+    /// can be answered by [`contains_filtered`](#method.contains_filtered) without
+    /// searching the set.
+    ///
+    /// The filter is sized for about `expected_items` values, it has more false
+    /// positives if the set grows beyond that.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::with_bloom_filter(100, 0.01);
+    /// ss.add(1);
+    /// ss.add(2);
+    /// ss.remove(&2);
+    /// assert!(ss.contains_filtered(&1));
+    /// assert!(!ss.contains_filtered(&2));
+    /// ```
+    #[cfg(feature = "bloom")]
+    pub fn with_bloom_filter(expected_items: usize, false_positive_rate: f64) -> Self
+    where
+        V: Hash,
+    {
+        let mut ss = Self::new();
+        ss.filter = Some(BloomFilter::new(expected_items, false_positive_rate));
+        ss
+    }
+
+    /// See [`SkipList::with_config`](crate::skiplist::SkipList::with_config)
+    pub fn with_config(p: f64, max_levels: usize) -> Self {
+        Self::with_level_generator(LevelGenerator::with_config(p, max_levels))
+    }
+
+    /// See [`SkipList::probability`](crate::skiplist::SkipList::probability)
+    pub fn probability(&self) -> f64 {
+        self.sk.probability()
+    }
+
+    /// Returns the maximum number of levels
+    pub fn max_levels(&self) -> usize {
+        self.sk.max_levels()
+    }
+
+    /// See [`SkipList::set_probability`](crate::skiplist::SkipList::set_probability)
+    pub fn set_probability(&mut self, p: f64) {
+        self.sk.set_probability(p)
+    }
+
+    /// Choose the levels of all elements again, see
+    /// [`SkipList::rebuild`](../skiplist/struct.SkipList.html#method.rebuild)
+    pub fn rebuild(&mut self) {
+        self.sk.rebuild()
+    }
+
+    /// Returns numbers of operations since the set is created or its
+    /// metrics are reset
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.sk.metrics()
+    }
+
+    /// Reset all metrics to 0
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&self) {
+        self.sk.reset_metrics()
+    }
+
     /// Add a value, returns the old value if it exists.
     ///
     /// # Examples
@@ -43,7 +157,69 @@ impl<V: Ord> SkipSet<V> {
     /// ```
     ///
     pub fn add(&mut self, value: V) -> Option<V> {
-        self.sk.insert(value)
+        let hash = self._filter_hash(&value);
+        let old = self.sk.insert(value);
+        if old.is_none() {
+            self._filter_insert(hash);
+        }
+        old
+    }
+
+    /// Add a value the caller guarantees isn't in the set, the check for an
+    /// equal value is skipped. It's only verified in debug builds, the set is
+    /// broken if the value exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::new();
+    /// for i in 0..10 {
+    ///     ss.insert_unchecked(i);
+    /// }
+    /// assert!(ss.contains(&3));
+    /// ```
+    pub fn insert_unchecked(&mut self, value: V) {
+        let hash = self._filter_hash(&value);
+        self.sk.insert_unique_unchecked(value);
+        self._filter_insert(hash);
+    }
+
+    /// Add values of the iterator, values that are greater than the last
+    /// element are appended at the tail in splices without being searched,
+    /// others are added one by one. Returns number of appended values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::new();
+    /// assert_eq!(ss.extend_from_sorted(vec![1, 2, 2, 3]), 3);
+    /// assert_eq!(ss.cardinal(), 3);
+    /// ```
+    pub fn extend_from_sorted<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = V>,
+    {
+        let mut pending = vec![];
+        let mut appended = 0;
+        for value in iter {
+            let in_order = match pending.last().or_else(|| self.sk.back()) {
+                None => true,
+                Some(last) => *last < value,
+            };
+            if in_order {
+                let hash = self._filter_hash(&value);
+                self._filter_insert(hash);
+                pending.push(value);
+            } else {
+                appended += self.sk.sk._insert_iter_at(self.sk.len(), pending.drain(..));
+                self.add(value);
+            }
+        }
+        appended + self.sk.sk._insert_iter_at(self.sk.len(), pending)
     }
 
     /// Get the value that match q
@@ -70,6 +246,80 @@ impl<V: Ord> SkipSet<V> {
         self.sk.get_first(q).map(|(_, v)| v)
     }
 
+    /// Returns the number of elements greater than q, which is the place of q
+    /// counted from the largest element starting at 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set = SkipSet::new();
+    /// for i in 0..5 {
+    ///     set.add(i);
+    /// }
+    /// assert_eq!(set.rank_desc(&4), 0);
+    /// assert_eq!(set.select_desc(set.rank_desc(&1)), Some(&1));
+    /// ```
+    pub fn rank_desc<Q: ?Sized>(&self, q: &Q) -> usize
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.rank_desc(q)
+    }
+
+    /// Returns the element at the place `k` counted from the largest element
+    /// starting at 0, or `None` if `k` is out of bounds
+    pub fn select_desc(&self, k: usize) -> Option<&V> {
+        self.sk.select_desc(k)
+    }
+
+    /// Returns the element at the index in ascending order, or `None` if the
+    /// index is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set = SkipSet::new();
+    /// for i in [5, 1, 9, 3, 7].iter() {
+    ///     set.add(*i);
+    /// }
+    /// assert_eq!(set.get_index(set.cardinal() / 2), Some(&5));
+    /// assert_eq!(set.get_index(5), None);
+    /// ```
+    pub fn get_index(&self, index: usize) -> Option<&V> {
+        self.sk.get(index)
+    }
+
+    /// Remove the element at the index in ascending order, returns `None` if
+    /// the index is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set = SkipSet::new();
+    /// for i in 0..100 {
+    ///     set.add(i);
+    /// }
+    /// // drop the 90th percentile member
+    /// assert_eq!(set.remove_index(set.cardinal() * 9 / 10), Some(90));
+    /// assert!(!set.contains(&90));
+    /// assert_eq!(set.remove_index(99), None);
+    /// ```
+    pub fn remove_index(&mut self, index: usize) -> Option<V> {
+        if index >= self.sk.len() {
+            return None;
+        }
+        let value = self.sk.remove(index);
+        self._filter_remove(&value);
+        Some(value)
+    }
+
     /// Remove the value that equals q, returns the value if an element is removed
     /// returns None if the element do not exist.
     pub fn remove<Q: ?Sized>(&mut self, q: &Q) -> Option<V>
@@ -77,7 +327,9 @@ impl<V: Ord> SkipSet<V> {
         V: Borrow<Q>,
         Q: Ord,
     {
-        self.sk.remove_first(q)
+        let value = self.sk.remove_first(q)?;
+        self._filter_remove(&value);
+        Some(value)
     }
 
     /// Check if the set contains the value.
@@ -89,6 +341,78 @@ impl<V: Ord> SkipSet<V> {
         self.get(q).is_some()
     }
 
+    /// Check if the set contains the value, the bloom filter is asked first and
+    /// the set is searched only if the value may be in it. Same as
+    /// [`contains`](#method.contains) if the set has no filter.
+    #[cfg(feature = "bloom")]
+    pub fn contains_filtered<Q: ?Sized>(&self, q: &Q) -> bool
+    where
+        V: Borrow<Q>,
+        Q: Ord + Hash,
+    {
+        match self.filter.as_ref() {
+            Some(filter) if !filter.may_contain_hash(bloom::hash_of(q)) => false,
+            _ => self.contains(q),
+        }
+    }
+
+    /// Check if the set contains all the values, stops at the first value not in
+    /// the set. Values can be in any order, ascending values are checked in one
+    /// walk of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::new();
+    /// for i in 0..10 {
+    ///     ss.add(i);
+    /// }
+    /// assert!(ss.contains_all(&[1, 3, 5]));
+    /// assert!(ss.contains_all(&[5, 3, 1]));
+    /// assert!(!ss.contains_all(&[1, 30]));
+    /// ```
+    pub fn contains_all<'q, Q: 'q + ?Sized, I>(&self, values: I) -> bool
+    where
+        I: IntoIterator<Item = &'q Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut seek_path = SeekPath::new(&self.sk.sk);
+        values
+            .into_iter()
+            .all(|q| seek_path.get_first_by(|v| v.borrow().cmp(q)).is_some())
+    }
+
+    /// Check if the set contains any of the values, stops at the first value in
+    /// the set. Values can be in any order, ascending values are checked in one
+    /// walk of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::new();
+    /// for i in 0..10 {
+    ///     ss.add(i);
+    /// }
+    /// assert!(ss.contains_any(&[30, 5]));
+    /// assert!(!ss.contains_any(&[10, 20]));
+    /// ```
+    pub fn contains_any<'q, Q: 'q + ?Sized, I>(&self, values: I) -> bool
+    where
+        I: IntoIterator<Item = &'q Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut seek_path = SeekPath::new(&self.sk.sk);
+        values
+            .into_iter()
+            .any(|q| seek_path.get_first_by(|v| v.borrow().cmp(q)).is_some())
+    }
+
     /// Returns cardinal of the set.
     pub fn cardinal(&self) -> usize {
         self.sk.len()
@@ -117,12 +441,16 @@ impl<V: Ord> SkipSet<V> {
 
     /// Remove the minimum value in the set
     pub fn remove_min(&mut self) -> Option<V> {
-        self.sk.pop_front()
+        let value = self.sk.pop_front()?;
+        self._filter_remove(&value);
+        Some(value)
     }
 
     /// Remove the maximum value in the set
     pub fn remove_max(&mut self) -> Option<V> {
-        self.sk.pop_back()
+        let value = self.sk.pop_back()?;
+        self._filter_remove(&value);
+        Some(value)
     }
 
     /// Returns graph that contains a range of elements of the skipset
@@ -135,6 +463,28 @@ impl<V: Ord> SkipSet<V> {
         self.sk.explain(range)
     }
 
+    /// Write the graph of `explain` to the writer, same as
+    /// [`SkipList::explain_to`](../skiplist/struct.SkipList.html#method.explain_to)
+    pub fn explain_to<R, W>(&self, range: R, w: &mut W) -> Result<(), &'static str>
+    where
+        V: std::fmt::Display,
+        R: RangeBounds<usize>,
+        W: std::fmt::Write,
+    {
+        self.sk.explain_to(range, w)
+    }
+
+    /// Returns graph that contains values in the range, same as
+    /// [`OrderedSkipList::explain_by_value`](../ordered_skiplist/struct.OrderedSkipList.html#method.explain_by_value)
+    pub fn explain_by_value<R, Q: ?Sized>(&self, range: R) -> Result<String, &'static str>
+    where
+        V: std::fmt::Display + Borrow<Q>,
+        R: ValueRange<Q>,
+        Q: Ord,
+    {
+        self.sk.explain_by_value(range)
+    }
+
     /// Returns an iterator for the set
     pub fn iter(&self) -> Iter<'_, V> {
         self.sk.iter()
@@ -146,15 +496,169 @@ impl<V: Ord> SkipSet<V> {
     ///
     /// The method will panic if the start_bounds is less than the end_bounds
     ///
-    pub fn range<'a, 'b, R, Q: 'b + ?Sized>(&'a self, range: R) -> Range<'a, V>
+    pub fn range<R, Q: ?Sized>(&self, range: R) -> Range<'_, V>
     where
-        R: RangeBounds<&'b Q>,
+        R: ValueRange<Q>,
         V: Borrow<Q>,
         Q: Ord,
     {
         self.sk.range(range)
     }
 
+    /// Returns at most `limit` elements starting from the index `offset`, and the
+    /// last of them as the continuation token if more elements follow
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set = SkipSet::new();
+    /// for i in 0..5 {
+    ///     set.add(i);
+    /// }
+    /// let (items, next) = set.page(0, 2);
+    /// assert_eq!(items, vec![&0, &1]);
+    /// let (items, next) = set.page_after(next.unwrap(), 2);
+    /// assert_eq!(items, vec![&2, &3]);
+    /// assert_eq!(set.page_after(next.unwrap(), 2), (vec![&4], None));
+    /// ```
+    pub fn page(&self, offset: usize, limit: usize) -> (Vec<&V>, Option<&V>) {
+        self.sk.page(offset, limit)
+    }
+
+    /// Returns at most `limit` elements greater than q, and the last of them as
+    /// the continuation token if more elements follow
+    pub fn page_after<Q: ?Sized>(&self, q: &Q, limit: usize) -> (Vec<&V>, Option<&V>)
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.page_after(q, limit)
+    }
+
+    /// Returns a set of the values mapped by `f`, values mapped to equal ones
+    /// are merged. If the mapped values are still ascending they are linked
+    /// without being sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(-3..3);
+    /// let doubled = set.map(|v| v * 2);
+    /// assert_eq!(doubled.iter().cloned().collect::<Vec<_>>(), vec![-6, -4, -2, 0, 2, 4]);
+    /// let squares = set.map(|v| v * v);
+    /// assert_eq!(squares.iter().cloned().collect::<Vec<_>>(), vec![0, 1, 4, 9]);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> SkipSet<U>
+    where
+        U: Ord,
+        F: FnMut(&V) -> U,
+    {
+        let mut values: Vec<U> = self.iter().map(f).collect();
+        if !values.windows(2).all(|w| w[0] < w[1]) {
+            values.sort();
+            values.dedup();
+        }
+        SkipSet::from_sorted_iter_unchecked(values)
+    }
+
+    /// Returns a set of the values for which `f` returns true
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(0..10);
+    /// let even = set.filter(|v| v % 2 == 0);
+    /// assert_eq!(even.iter().cloned().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+    /// ```
+    pub fn filter<F>(&self, mut f: F) -> SkipSet<V>
+    where
+        V: Clone,
+        F: FnMut(&V) -> bool,
+    {
+        SkipSet::from_sorted_iter_unchecked(self.iter().filter(|v| f(v)).cloned())
+    }
+
+    /// Returns an iterator of at most `before` elements less than q, q itself if
+    /// it's in the set, and at most `after` elements greater than q
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set = SkipSet::new();
+    /// for i in 0..10 {
+    ///     set.add(i);
+    /// }
+    /// let around: Vec<_> = set.neighbors(&5, 2, 2).cloned().collect();
+    /// assert_eq!(around, vec![3, 4, 5, 6, 7]);
+    /// ```
+    pub fn neighbors<Q: ?Sized>(&self, q: &Q, before: usize, after: usize) -> Range<'_, V>
+    where
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.neighbors(q, before, after)
+    }
+
+    /// Returns an iterator of the element at the index with at most `before`
+    /// elements before it and at most `after` elements after it
+    pub fn neighbors_of_rank(&self, index: usize, before: usize, after: usize) -> Range<'_, V> {
+        self.sk.neighbors_of_rank(index, before, after)
+    }
+
+    /// Returns whether any element is in the range, with one search
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(vec![10, 20]);
+    /// assert!(set.any_in_range(&5..&15));
+    /// assert!(!set.any_in_range(&11..&20));
+    /// ```
+    pub fn any_in_range<R, Q: ?Sized>(&self, range: R) -> bool
+    where
+        R: ValueRange<Q>,
+        V: Borrow<Q>,
+        Q: Ord,
+    {
+        self.sk.any_in_range(range)
+    }
+
+    /// Returns a set with clones of the elements in the range, the elements are
+    /// cloned in one traversal and linked in one splice. The new set has no
+    /// bloom filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if start_bound is greater than end_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(0..10);
+    /// let part = set.clone_range(&7..);
+    /// assert_eq!(part.iter().cloned().collect::<Vec<_>>(), vec![7, 8, 9]);
+    /// ```
+    pub fn clone_range<R, Q: ?Sized>(&self, range: R) -> SkipSet<V>
+    where
+        R: ValueRange<Q>,
+        V: Borrow<Q> + Clone,
+        Q: Ord,
+    {
+        self.sk.clone_range(range).into_set()
+    }
+
     /// Returns a lazy iterator producing elements in the symmetric difference of `SkipSet`s.
     ///
     /// # Examples
@@ -174,6 +678,7 @@ impl<V: Ord> SkipSet<V> {
     /// assert_eq!(arr, vec![0, 10]);
     /// ```
     pub fn symmetric_difference<'a>(&'a self, rhs: &'a SkipSet<V>) -> SymmetricDifference<'a, V> {
+        trace_event!(lhs = self.cardinal(), rhs = rhs.cardinal(), "symmetric difference");
         let mut lhs_iter = self.iter();
         let mut rhs_iter = rhs.iter();
         SymmetricDifference {
@@ -205,8 +710,10 @@ impl<V: Ord> SkipSet<V> {
     pub fn difference<'a>(&'a self, rhs: &'a SkipSet<V>) -> Difference<'a, V> {
         // Use the search method if lhs's cardinal is much smaller than rhs's
         if self.cardinal() * rhs.levels() < rhs.cardinal() {
+            trace_event!(lhs = self.cardinal(), rhs = rhs.cardinal(), "difference by searching");
             return self.difference_search(rhs);
         }
+        trace_event!(lhs = self.cardinal(), rhs = rhs.cardinal(), "difference by traversing");
         // else use the traverse method
         self.difference_traverse(rhs)
     }
@@ -301,8 +808,10 @@ impl<V: Ord> SkipSet<V> {
         }
 
         if lhs.cardinal() * rhs.levels() < rhs.cardinal() {
+            trace_event!(lhs = lhs.cardinal(), rhs = rhs.cardinal(), "intersection by searching");
             return lhs.intersection_search(rhs);
         }
+        trace_event!(lhs = lhs.cardinal(), rhs = rhs.cardinal(), "intersection by traversing");
 
         lhs.intersection_traverse(rhs)
     }
@@ -388,6 +897,7 @@ impl<V: Ord> SkipSet<V> {
     /// assert_eq!(arr, (0..11).collect::<Vec<i32>>());
     /// ```
     pub fn union<'a>(&'a self, rhs: &'a SkipSet<V>) -> Union<'a, V> {
+        trace_event!(lhs = self.cardinal(), rhs = rhs.cardinal(), "union");
         let mut lhs_iter = self.iter();
         let mut rhs_iter = rhs.iter();
         Union {
@@ -449,6 +959,212 @@ impl<V: Ord> SkipSet<V> {
     fn levels(&self) -> usize {
         self.sk.sk.head.links.len()
     }
+
+    // Every value added to or removed from the set should go through these,
+    // they do nothing if the set has no bloom filter.
+
+    fn _filter_hash(&self, _value: &V) -> Option<u64> {
+        #[cfg(feature = "bloom")]
+        if let Some(filter) = self.filter.as_ref() {
+            return Some(filter.hash(_value));
+        }
+        None
+    }
+
+    fn _filter_insert(&mut self, _hash: Option<u64>) {
+        #[cfg(feature = "bloom")]
+        if let (Some(filter), Some(hash)) = (self.filter.as_mut(), _hash) {
+            filter.insert_hash(hash);
+        }
+    }
+
+    fn _filter_remove(&mut self, _value: &V) {
+        #[cfg(feature = "bloom")]
+        if let Some(filter) = self.filter.as_mut() {
+            filter.remove_hash(filter.hash(_value));
+        }
+    }
+}
+
+impl<V: Ord + std::fmt::Debug> std::fmt::Debug for SkipSet<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<V: Ord> PartialEq for SkipSet<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cardinal() == other.cardinal() && self.iter().eq(other.iter())
+    }
+}
+
+impl<V: Ord> Eq for SkipSet<V> {}
+
+impl<V: Ord + std::hash::Hash> std::hash::Hash for SkipSet<V> {
+    /// Hash the length and values in order, equal sets have equal hashes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut memo = HashMap::new();
+    /// let mut set = SkipSet::new();
+    /// set.add(2);
+    /// set.add(1);
+    /// memo.insert(set, "computed");
+    ///
+    /// let mut key = SkipSet::new();
+    /// key.add(1);
+    /// key.add(2);
+    /// assert_eq!(memo.get(&key), Some(&"computed"));
+    /// ```
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.cardinal());
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+/// Maximum number of values a set displays by default
+const DISPLAY_LIMIT: usize = 10;
+
+impl<V: Ord + std::fmt::Display> std::fmt::Display for SkipSet<V> {
+    /// Formats the set as `{a, b, c}`, sets larger than 10 values are shortened
+    /// to their first values and the number of values. The precision, like
+    /// `{:.3}`, changes the number of values to show.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut ss = SkipSet::new();
+    /// for i in 0..3 {
+    ///     ss.add(i);
+    /// }
+    /// assert_eq!(format!("{}", ss), "{0, 1, 2}");
+    /// assert_eq!(format!("{:?}", ss), "{0, 1, 2}");
+    ///
+    /// for i in 3..100 {
+    ///     ss.add(i);
+    /// }
+    /// assert_eq!(format!("{:.2}", ss), "{0, 1, … (100 elements)}");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let limit = f.precision().unwrap_or(DISPLAY_LIMIT);
+        write!(f, "{{")?;
+        for (i, value) in self.iter().take(limit).enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        if self.cardinal() > limit {
+            if limit != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "… ({} elements)", self.cardinal())?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<V: Ord> From<SkipMap<V, ()>> for SkipSet<V> {
+    /// Convert a map with empty values to a set of its keys, the keys are linked
+    /// in order in one splice without being compared
+    fn from(map: SkipMap<V, ()>) -> Self {
+        SkipSet::from_sorted_iter_unchecked(map.into_keys())
+    }
+}
+
+impl<V: Ord> OrderedSkipList<V> {
+    /// Convert the ordered skiplist to a set, duplicated elements are removed
+    /// and the nodes are reused
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::ordered_skiplist::OrderedSkipList;
+    ///
+    /// let mut sk = OrderedSkipList::new_duplicatable();
+    /// for i in [2, 1, 2, 3, 1].iter() {
+    ///     sk.insert(*i);
+    /// }
+    /// let set = sk.into_set();
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn into_set(mut self) -> SkipSet<V> {
+        if self.duplicatable {
+            self.dedup();
+            self.duplicatable = false;
+        }
+        SkipSet {
+            sk: self,
+            #[cfg(feature = "bloom")]
+            filter: None,
+        }
+    }
+}
+
+impl<V: Ord> SkipSet<V> {
+    /// Convert the set to an ordered skiplist that doesn't allow duplicated
+    /// elements, the nodes are reused and the bloom filter is dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let set = SkipSet::from_sorted_iter_unchecked(0..3);
+    /// let sk = set.into_ordered_list();
+    /// assert_eq!(sk.get(2), Some(&2));
+    /// ```
+    pub fn into_ordered_list(self) -> OrderedSkipList<V> {
+        self.sk
+    }
+}
+
+impl<V: Ord> From<OrderedSkipList<V>> for SkipSet<V> {
+    /// Same as [`OrderedSkipList::into_set`]
+    fn from(sk: OrderedSkipList<V>) -> Self {
+        sk.into_set()
+    }
+}
+
+impl<V: Ord> From<SkipSet<V>> for OrderedSkipList<V> {
+    /// Same as [`SkipSet::into_ordered_list`]
+    fn from(set: SkipSet<V>) -> Self {
+        set.into_ordered_list()
+    }
+}
+
+impl<V: Ord> Extend<V> for SkipSet<V> {
+    /// Add values of the iterator one by one
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<'a, V: Ord + Copy + 'a> Extend<&'a V> for SkipSet<V> {
+    /// Add copies of values of the iterator one by one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::skipset::SkipSet;
+    ///
+    /// let mut set: SkipSet<i32> = SkipSet::new();
+    /// set.extend([3, 1, 3].iter());
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![1, 3]);
+    /// ```
+    fn extend<I: IntoIterator<Item = &'a V>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
 }
 
 impl<V: Ord> IntoIterator for SkipSet<V> {
@@ -484,6 +1200,7 @@ impl<V: Ord> IntoIterator for SkipSet<V> {
 ///
 /// [`SkipSet`]: struct.SkipSet.html
 /// [`symmetric_difference`]: struct.SkipSet.html#method.symmetric_difference
+#[derive(Debug)]
 pub struct SymmetricDifference<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs_iter: Iter<'a, V>,
@@ -491,6 +1208,17 @@ pub struct SymmetricDifference<'a, V: Ord> {
     rhs_value: Option<&'a V>,
 }
 
+impl<'a, V: Ord> Clone for SymmetricDifference<'a, V> {
+    fn clone(&self) -> Self {
+        SymmetricDifference {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for SymmetricDifference<'a, V> {
     type Item = &'a V;
 
@@ -534,13 +1262,26 @@ impl<'a, V: Ord> Iterator for SymmetricDifference<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for SymmetricDifference<'a, V> {}
+
 #[doc(hidden)]
+#[derive(Debug)]
 pub struct DifferenceTraverse<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs_iter: Iter<'a, V>,
     rhs_value: Option<&'a V>,
 }
 
+impl<'a, V: Ord> Clone for DifferenceTraverse<'a, V> {
+    fn clone(&self) -> Self {
+        DifferenceTraverse {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for DifferenceTraverse<'a, V> {
     type Item = &'a V;
 
@@ -564,12 +1305,31 @@ impl<'a, V: Ord> Iterator for DifferenceTraverse<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for DifferenceTraverse<'a, V> {}
+
 #[doc(hidden)]
 pub struct DifferenceSearch<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs: &'a SkipSet<V>,
 }
 
+impl<'a, V: Ord> Clone for DifferenceSearch<'a, V> {
+    fn clone(&self) -> Self {
+        DifferenceSearch {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs: self.rhs,
+        }
+    }
+}
+
+impl<'a, V: Ord + std::fmt::Debug> std::fmt::Debug for DifferenceSearch<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DifferenceSearch")
+            .field("lhs_iter", &self.lhs_iter)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<'a, V: Ord> Iterator for DifferenceSearch<'a, V> {
     type Item = &'a V;
 
@@ -589,6 +1349,8 @@ impl<'a, V: Ord> Iterator for DifferenceSearch<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for DifferenceSearch<'a, V> {}
+
 /// A lazy iterator producing elements in the difference of `SkipSet`'s.
 ///
 /// This `struct` is created by the [`difference`] method on
@@ -596,11 +1358,21 @@ impl<'a, V: Ord> Iterator for DifferenceSearch<'a, V> {
 ///
 /// [`SkipSet`]: struct.SkipSet.html
 /// [`difference`]: struct.SkipSet.html#method.difference
+#[derive(Debug)]
 pub enum Difference<'a, V: Ord> {
     Traverse(DifferenceTraverse<'a, V>),
     Search(DifferenceSearch<'a, V>),
 }
 
+impl<'a, V: Ord> Clone for Difference<'a, V> {
+    fn clone(&self) -> Self {
+        match self {
+            Difference::Traverse(d) => Difference::Traverse(d.clone()),
+            Difference::Search(d) => Difference::Search(d.clone()),
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for Difference<'a, V> {
     type Item = &'a V;
 
@@ -612,7 +1384,10 @@ impl<'a, V: Ord> Iterator for Difference<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for Difference<'a, V> {}
+
 #[doc(hidden)]
+#[derive(Debug)]
 pub struct IntersectionTraverse<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs_iter: Iter<'a, V>,
@@ -620,6 +1395,17 @@ pub struct IntersectionTraverse<'a, V: Ord> {
     rhs_value: Option<&'a V>,
 }
 
+impl<'a, V: Ord> Clone for IntersectionTraverse<'a, V> {
+    fn clone(&self) -> Self {
+        IntersectionTraverse {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for IntersectionTraverse<'a, V> {
     type Item = &'a V;
 
@@ -648,12 +1434,31 @@ impl<'a, V: Ord> Iterator for IntersectionTraverse<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for IntersectionTraverse<'a, V> {}
+
 #[doc(hidden)]
 pub struct IntersectionSearch<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs: &'a SkipSet<V>,
 }
 
+impl<'a, V: Ord> Clone for IntersectionSearch<'a, V> {
+    fn clone(&self) -> Self {
+        IntersectionSearch {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs: self.rhs,
+        }
+    }
+}
+
+impl<'a, V: Ord + std::fmt::Debug> std::fmt::Debug for IntersectionSearch<'a, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("IntersectionSearch")
+            .field("lhs_iter", &self.lhs_iter)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<'a, V: Ord> Iterator for IntersectionSearch<'a, V> {
     type Item = &'a V;
 
@@ -673,6 +1478,8 @@ impl<'a, V: Ord> Iterator for IntersectionSearch<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for IntersectionSearch<'a, V> {}
+
 /// A lazy iterator producing elements in the intersection of `SkipSet`'s.
 ///
 /// This `struct` is created by the [`intersection`] method on
@@ -680,11 +1487,21 @@ impl<'a, V: Ord> Iterator for IntersectionSearch<'a, V> {
 ///
 /// [`SkipSet`]: struct.SkipSet.html
 /// [`intersection`]: struct.SkipSet.html#method.intersection
+#[derive(Debug)]
 pub enum Intersection<'a, V: Ord> {
     Traverse(IntersectionTraverse<'a, V>),
     Search(IntersectionSearch<'a, V>),
 }
 
+impl<'a, V: Ord> Clone for Intersection<'a, V> {
+    fn clone(&self) -> Self {
+        match self {
+            Intersection::Traverse(d) => Intersection::Traverse(d.clone()),
+            Intersection::Search(d) => Intersection::Search(d.clone()),
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for Intersection<'a, V> {
     type Item = &'a V;
 
@@ -696,6 +1513,8 @@ impl<'a, V: Ord> Iterator for Intersection<'a, V> {
     }
 }
 
+impl<'a, V: Ord> FusedIterator for Intersection<'a, V> {}
+
 /// A lazy iterator producing elements in the union of `SkipSet`'s.
 ///
 /// This `struct` is created by the [`union`] method on
@@ -703,6 +1522,7 @@ impl<'a, V: Ord> Iterator for Intersection<'a, V> {
 ///
 /// [`SkipSet`]: struct.SkipSet.html
 /// [`union`]: struct.SkipSet.html#method.union
+#[derive(Debug)]
 pub struct Union<'a, V: Ord> {
     lhs_iter: Iter<'a, V>,
     rhs_iter: Iter<'a, V>,
@@ -710,6 +1530,17 @@ pub struct Union<'a, V: Ord> {
     rhs_value: Option<&'a V>,
 }
 
+impl<'a, V: Ord> Clone for Union<'a, V> {
+    fn clone(&self) -> Self {
+        Union {
+            lhs_iter: self.lhs_iter.clone(),
+            rhs_iter: self.rhs_iter.clone(),
+            lhs_value: self.lhs_value,
+            rhs_value: self.rhs_value,
+        }
+    }
+}
+
 impl<'a, V: Ord> Iterator for Union<'a, V> {
     type Item = &'a V;
 
@@ -753,3 +1584,5 @@ impl<'a, V: Ord> Iterator for Union<'a, V> {
         None
     }
 }
+
+impl<'a, V: Ord> FusedIterator for Union<'a, V> {}
@@ -0,0 +1,181 @@
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+/// A range of values for ranges of ordered containers.
+///
+/// It's implemented for ranges of references like `&low..&high`, ranges of
+/// primitive values like `low..high`, and `(Bound, Bound)` tuples of both, so
+/// mixed inclusive and exclusive bounds can be expressed.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::ordered_skiplist::OrderedSkipList;
+/// use std::ops::Bound;
+///
+/// let mut sk = OrderedSkipList::new();
+/// for i in 0..10 {
+///     sk.insert(i);
+/// }
+///
+/// let values: Vec<_> = sk.range(2..5).cloned().collect();
+/// assert_eq!(values, vec![2, 3, 4]);
+///
+/// let values: Vec<_> = sk.range((Bound::Excluded(&2), Bound::Included(&5))).cloned().collect();
+/// assert_eq!(values, vec![3, 4, 5]);
+/// ```
+pub trait ValueRange<Q: ?Sized> {
+    /// Start bound of the range
+    fn start_bound(&self) -> Bound<&Q>;
+
+    /// End bound of the range
+    fn end_bound(&self) -> Bound<&Q>;
+}
+
+impl<Q: ?Sized> ValueRange<Q> for Range<&Q> {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Included(self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Excluded(self.end)
+    }
+}
+
+impl<Q: ?Sized> ValueRange<Q> for RangeInclusive<&Q> {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Included(self.start())
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Included(self.end())
+    }
+}
+
+impl<Q: ?Sized> ValueRange<Q> for RangeFrom<&Q> {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Included(self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Unbounded
+    }
+}
+
+impl<Q: ?Sized> ValueRange<Q> for RangeTo<&Q> {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Unbounded
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Excluded(self.end)
+    }
+}
+
+impl<Q: ?Sized> ValueRange<Q> for RangeToInclusive<&Q> {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Unbounded
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Included(self.end)
+    }
+}
+
+impl<Q: ?Sized> ValueRange<Q> for (Bound<&Q>, Bound<&Q>) {
+    fn start_bound(&self) -> Bound<&Q> {
+        self.0
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        self.1
+    }
+}
+
+// Ranges of values are only implemented for primitive types, a blanket
+// implementation for `Copy` types makes `&low..&high` ambiguous for values
+// like `String` that borrow as more than one type.
+macro_rules! impl_value_range_by_value {
+    ($($t:ty)*) => {
+        $(
+            impl ValueRange<$t> for Range<$t> {
+                fn start_bound(&self) -> Bound<&$t> {
+                    Bound::Included(&self.start)
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    Bound::Excluded(&self.end)
+                }
+            }
+
+            impl ValueRange<$t> for RangeInclusive<$t> {
+                fn start_bound(&self) -> Bound<&$t> {
+                    Bound::Included(self.start())
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    Bound::Included(self.end())
+                }
+            }
+
+            impl ValueRange<$t> for RangeFrom<$t> {
+                fn start_bound(&self) -> Bound<&$t> {
+                    Bound::Included(&self.start)
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    Bound::Unbounded
+                }
+            }
+
+            impl ValueRange<$t> for RangeTo<$t> {
+                fn start_bound(&self) -> Bound<&$t> {
+                    Bound::Unbounded
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    Bound::Excluded(&self.end)
+                }
+            }
+
+            impl ValueRange<$t> for RangeToInclusive<$t> {
+                fn start_bound(&self) -> Bound<&$t> {
+                    Bound::Unbounded
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    Bound::Included(&self.end)
+                }
+            }
+
+            impl ValueRange<$t> for (Bound<$t>, Bound<$t>) {
+                fn start_bound(&self) -> Bound<&$t> {
+                    bound_ref(&self.0)
+                }
+
+                fn end_bound(&self) -> Bound<&$t> {
+                    bound_ref(&self.1)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_range_by_value!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize char bool);
+
+impl<Q: ?Sized> ValueRange<Q> for RangeFull {
+    fn start_bound(&self) -> Bound<&Q> {
+        Bound::Unbounded
+    }
+
+    fn end_bound(&self) -> Bound<&Q> {
+        Bound::Unbounded
+    }
+}
+
+fn bound_ref<Q>(bound: &Bound<Q>) -> Bound<&Q> {
+    match bound {
+        Bound::Included(q) => Bound::Included(q),
+        Bound::Excluded(q) => Bound::Excluded(q),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}